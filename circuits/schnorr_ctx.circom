@@ -0,0 +1,5 @@
+pragma circom 2.1.6;
+
+include "schnorr_verify.circom";
+
+component main {public [pkX, pkY, msgHash, ctxTag]} = SchnorrVerifyWithContext();
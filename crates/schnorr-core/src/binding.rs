@@ -0,0 +1,112 @@
+// crates/schnorr-core/src/binding.rs
+//
+// Deployment binding for signatures (EIP-712-style domain).
+//
+// A `ChainBinding` names the chain and verifier contract a signature is
+// meant for.  It is folded into the message hash as a context tag:
+//
+//   domainTag = Poseidon(chainId, verifyingContract)
+//   msgHash'  = Poseidon(domainTag, H(m))
+//
+// so a signature produced for one deployment fails verification — in Rust
+// and in `circuits/schnorr_chain.circom` — against any other.
+// `verifyingContract` is the 20-byte address read as a big-endian integer.
+
+use ark_bn254::Fr as Bn254Fr;
+use ark_ff::PrimeField;
+
+use crate::curve::bn254_to_dec_string;
+use crate::hash::bind_context;
+
+/// The deployment a signature is bound to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ChainBinding {
+    pub chain_id: u64,
+    pub verifying_contract: [u8; 20],
+}
+
+impl ChainBinding {
+    pub fn new(chain_id: u64, verifying_contract: [u8; 20]) -> Self {
+        ChainBinding {
+            chain_id,
+            verifying_contract,
+        }
+    }
+
+    /// Parse a `0x`-prefixed (or bare) 40-hex-digit contract address.
+    pub fn from_hex_address(chain_id: u64, address: &str) -> Option<Self> {
+        let hex = address.strip_prefix("0x").unwrap_or(address);
+        if hex.len() != 40 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let mut contract = [0u8; 20];
+        for (i, byte) in contract.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(hex.get(2 * i..2 * i + 2)?, 16).ok()?;
+        }
+        Some(Self::new(chain_id, contract))
+    }
+
+    /// The chain id as a BN254 field element (circuit input `chainId`).
+    pub fn chain_id_field(&self) -> Bn254Fr {
+        Bn254Fr::from(self.chain_id)
+    }
+
+    /// The contract address as a BN254 field element
+    /// (circuit input `verifyingContract`).
+    pub fn contract_field(&self) -> Bn254Fr {
+        Bn254Fr::from_be_bytes_mod_order(&self.verifying_contract)
+    }
+
+    /// Domain tag Poseidon(chainId, verifyingContract), used as the
+    /// context tag when signing and verifying.
+    pub fn context_tag(&self) -> Bn254Fr {
+        bind_context(&self.chain_id_field(), &self.contract_field())
+    }
+
+    /// `0x`-prefixed lowercase hex address.
+    pub fn contract_hex(&self) -> String {
+        let hex: String = self.verifying_contract.iter().map(|b| format!("{b:02x}")).collect();
+        format!("0x{hex}")
+    }
+
+    /// Decimal strings for the `chainId` and `verifyingContract` circuit inputs.
+    pub fn to_dec_strings(&self) -> (String, String) {
+        (
+            bn254_to_dec_string(&self.chain_id_field()),
+            bn254_to_dec_string(&self.contract_field()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+
+    #[test]
+    fn parse_address() {
+        let b = ChainBinding::from_hex_address(1, CONTRACT).unwrap();
+        assert_eq!(b.contract_hex(), CONTRACT.to_lowercase());
+        assert!(ChainBinding::from_hex_address(1, "0x1234").is_none());
+        assert!(ChainBinding::from_hex_address(1, &format!("0x{}", "zz".repeat(20))).is_none());
+        assert!(ChainBinding::from_hex_address(1, &format!("0x{}", "+f".repeat(20))).is_none());
+    }
+
+    #[test]
+    fn contract_field_is_big_endian() {
+        let mut addr = [0u8; 20];
+        addr[19] = 0x2a;
+        assert_eq!(ChainBinding::new(1, addr).contract_field(), Bn254Fr::from(42u64));
+    }
+
+    #[test]
+    fn tag_depends_on_chain_and_contract() {
+        let a = ChainBinding::from_hex_address(1, CONTRACT).unwrap();
+        let b = ChainBinding::from_hex_address(5, CONTRACT).unwrap();
+        let mut c = a;
+        c.verifying_contract[0] ^= 1;
+        assert_ne!(a.context_tag(), b.context_tag());
+        assert_ne!(a.context_tag(), c.context_tag());
+    }
+}
@@ -80,6 +80,19 @@ impl BjjPoint {
         self.x == Fq::from(0u64) && self.y == Fq::from(1u64)
     }
 
+    /// Check if this point is in the prime-order subgroup: on the curve
+    /// and n·P = O.  The identity is in the subgroup.
+    pub fn is_in_subgroup(&self) -> bool {
+        self.is_on_curve() && self.mul_by_bn254_scalar(&field_from_dec_str(BJJ_ORDER)).is_zero()
+    }
+
+    /// Check if this point has order exactly n: in the subgroup and not the
+    /// identity.  Public keys and signature commitments must be such points;
+    /// a low-order key lets the signer forge signatures for any message.
+    pub fn is_prime_order(&self) -> bool {
+        !self.is_zero() && self.is_in_subgroup()
+    }
+
     /// Twisted Edwards point addition.
     ///
     /// (x1,y1) + (x2,y2) = (x3,y3) where:
@@ -150,6 +163,69 @@ impl BjjPoint {
     pub fn coords(&self) -> (Fq, Fq) {
         (self.x, self.y)
     }
+
+    /// Compress to 32 bytes, matching circomlibjs `babyJub.packPoint`.
+    ///
+    /// Layout: y as 32 little-endian bytes, with the top bit of the last
+    /// byte set iff x > (p − 1) / 2.
+    pub fn pack(&self) -> [u8; 32] {
+        let mut buf = [0u8; 32];
+        let y_bytes = self.y.into_bigint().to_bytes_le();
+        buf[..y_bytes.len()].copy_from_slice(&y_bytes);
+        if is_negative(&self.x) {
+            buf[31] |= 0x80;
+        }
+        buf
+    }
+
+    /// Decompress a point produced by [`BjjPoint::pack`] or circomlibjs
+    /// `babyJub.packPoint`.  Returns `None` if the bytes do not encode a
+    /// point of the prime-order subgroup, or set the sign bit for x = 0
+    /// (which `pack` never does), so every point has exactly one encoding.
+    ///
+    /// x is recovered from  x^2 = (1 − y^2) / (a − d·y^2).
+    pub fn unpack(packed: &[u8; 32]) -> Option<BjjPoint> {
+        let mut y_bytes = *packed;
+        let sign = y_bytes[31] & 0x80 != 0;
+        y_bytes[31] &= 0x7f;
+
+        let y_int = BigUint::from_bytes_le(&y_bytes);
+        let p = BigUint::from_bytes_le(&Fq::MODULUS.to_bytes_le());
+        if y_int >= p {
+            return None;
+        }
+        let y = Fq::from_le_bytes_mod_order(&y_bytes);
+
+        let a = Fq::from(A_COEFF);
+        let d = Fq::from(D_COEFF);
+        let y2 = y * y;
+        let x2 = (Fq::from(1u64) - y2) * (a - d * y2).inverse()?;
+        let mut x = x2.sqrt()?;
+        if x == Fq::from(0u64) && sign {
+            return None;
+        }
+
+        // Normalize to the "positive" root, then apply the sign bit.
+        if is_negative(&x) {
+            x = -x;
+        }
+        if sign {
+            x = -x;
+        }
+
+        let point = BjjPoint { x, y };
+        if point.is_in_subgroup() {
+            Some(point)
+        } else {
+            None
+        }
+    }
+}
+
+/// circomlibjs sign convention: a field element is "negative" iff it is
+/// greater than (p − 1) / 2.
+fn is_negative(f: &Fq) -> bool {
+    f.into_bigint() > Fq::MODULUS_MINUS_ONE_DIV_TWO
 }
 
 impl PartialEq for BjjPoint {
@@ -180,6 +256,16 @@ impl BjjScalar {
         self.0.into_bigint().to_bytes_le()
     }
 
+    /// Parse a canonical decimal string (must be < n).
+    pub fn from_dec_str(s: &str) -> Option<Self> {
+        let value: BigUint = s.parse().ok()?;
+        let n: BigUint = BJJ_ORDER.parse().expect("invalid BJJ_ORDER");
+        if value >= n {
+            return None;
+        }
+        Some(Self(Fr::from_le_bytes_mod_order(&value.to_bytes_le())))
+    }
+
     /// Convert to a decimal string (for JSON / witness export).
     pub fn to_dec_string(&self) -> String {
         let bytes = self.0.into_bigint().to_bytes_le();
@@ -221,6 +307,16 @@ pub fn bn254_to_dec_string(f: &Fq) -> String {
     BigUint::from_bytes_le(&bytes).to_string()
 }
 
+/// Parse a canonical decimal BN254 Fr element (must already be < p).
+pub fn bn254_from_dec_str(s: &str) -> Option<Fq> {
+    let value: BigUint = s.parse().ok()?;
+    let p = BigUint::from_bytes_le(&Fq::MODULUS.to_bytes_le());
+    if value >= p {
+        return None;
+    }
+    Some(Fq::from_le_bytes_mod_order(&value.to_bytes_le()))
+}
+
 /// Get little-endian bits of a BN254 field element.
 fn bn254_to_bits_le(f: &Fq) -> Vec<bool> {
     let bigint = f.into_bigint();
@@ -344,4 +440,42 @@ mod tests {
         let result = g.scalar_mul(&n);
         assert!(result.is_zero(), "n*G must be identity");
     }
+
+    #[test]
+    fn pack_unpack_roundtrip() {
+        let g = BjjPoint::generator();
+        for k in [1u64, 2, 7, 123456789] {
+            let p = g.scalar_mul(&BjjScalar(Fr::from(k)));
+            let packed = p.pack();
+            assert_eq!(BjjPoint::unpack(&packed), Some(p));
+        }
+    }
+
+    #[test]
+    fn unpack_rejects_signed_zero_x() {
+        let identity = BjjPoint::identity();
+        let mut packed = identity.pack();
+        assert_eq!(BjjPoint::unpack(&packed), Some(identity));
+        packed[31] |= 0x80;
+        assert_eq!(BjjPoint::unpack(&packed), None);
+    }
+
+    #[test]
+    fn unpack_rejects_points_outside_subgroup() {
+        // (0, -1) has order 2; adding it to G leaves the subgroup.
+        let two_torsion = BjjPoint { x: Fq::from(0u64), y: -Fq::from(1u64) };
+        let mixed = BjjPoint::generator().add(&two_torsion);
+        assert!(mixed.is_on_curve() && !mixed.is_in_subgroup());
+        assert_eq!(BjjPoint::unpack(&mixed.pack()), None);
+        assert_eq!(BjjPoint::unpack(&two_torsion.pack()), None);
+        assert!(BjjPoint::generator().is_prime_order());
+        assert!(!BjjPoint::identity().is_prime_order());
+    }
+
+    #[test]
+    fn unpack_rejects_non_canonical_y() {
+        // y = 2^255 - 1 with the sign bit cleared is still >= p
+        let packed = [0xFFu8; 32];
+        assert_eq!(BjjPoint::unpack(&packed), None);
+    }
 }
@@ -0,0 +1,236 @@
+// crates/schnorr-core/src/envelope.rs
+//
+// A signed message as handed between services: the message, the signer's
+// public key, the signature and the domain it was signed under.  Everything
+// a verifier (or the witness builder) needs, and no secret material.
+//
+// Wire form (canonical JSON, keys sorted, no whitespace):
+//
+//   {"domain":{"type":"none"},
+//    "message":"<hex>",
+//    "publicKey":["<x>","<y>"],
+//    "signature":<Signature::to_canonical_json>}
+//
+// with domain {"context":"<hex>","type":"context"} or
+// {"chainId":"<dec>","type":"chain","verifyingContract":"0x<hex>"}.
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::binding::ChainBinding;
+use crate::keypair::{KeyPair, PublicKey};
+use crate::sign::Signature;
+use crate::signer::{SchnorrSigner, SignError, SigningPurpose};
+use crate::verify::{verify, verify_with_chain_binding, verify_with_context, VerifyResult};
+
+/// What a signature is bound to besides the message.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum SigningDomain {
+    /// Plain [`Signature::sign`].
+    #[default]
+    None,
+    /// [`Signature::sign_with_context`] with this context string.
+    Context(Vec<u8>),
+    /// [`Signature::sign_with_chain_binding`] with this deployment.
+    Chain(ChainBinding),
+}
+
+/// A message together with its signature and public key.
+#[derive(Clone, Debug)]
+pub struct SignedMessage {
+    pub message: Vec<u8>,
+    pub public_key: PublicKey,
+    pub signature: Signature,
+    pub domain: SigningDomain,
+}
+
+impl SignedMessage {
+    /// Sign `message` under `domain` with a bare keypair and wrap the result.
+    pub fn sign(keypair: &KeyPair, message: &[u8], domain: SigningDomain) -> Self {
+        Self::sign_with(keypair, message, domain, SigningPurpose::General)
+            .expect("a bare keypair signs for any purpose")
+    }
+
+    /// Sign `message` under `domain` through a signer and its policy.
+    pub fn sign_with<S: SchnorrSigner + ?Sized>(
+        signer: &S,
+        message: &[u8],
+        domain: SigningDomain,
+        purpose: SigningPurpose,
+    ) -> Result<Self, SignError> {
+        let signature = match &domain {
+            SigningDomain::None => signer.sign(message, purpose)?,
+            SigningDomain::Context(ctx) => signer.sign_with_context(ctx, message, purpose)?,
+            SigningDomain::Chain(binding) => {
+                signer.sign_with_chain_binding(binding, message, purpose)?
+            }
+        };
+        Ok(SignedMessage {
+            message: message.to_vec(),
+            public_key: signer.public_key().clone(),
+            signature,
+            domain,
+        })
+    }
+
+    /// Verify the signature against the envelope's own key and domain.
+    pub fn verify(&self) -> VerifyResult {
+        let (sig, msg, pk) = (&self.signature, self.message.as_slice(), &self.public_key);
+        match &self.domain {
+            SigningDomain::None => verify(sig, msg, pk),
+            SigningDomain::Context(ctx) => verify_with_context(sig, ctx, msg, pk),
+            SigningDomain::Chain(binding) => verify_with_chain_binding(sig, binding, msg, pk),
+        }
+    }
+
+    /// The canonical wire form (see the module header).
+    pub fn to_canonical_json(&self) -> String {
+        let domain = match &self.domain {
+            SigningDomain::None => r#"{"type":"none"}"#.to_string(),
+            SigningDomain::Context(ctx) => {
+                format!(r#"{{"context":"{}","type":"context"}}"#, to_hex(ctx))
+            }
+            SigningDomain::Chain(binding) => format!(
+                r#"{{"chainId":"{}","type":"chain","verifyingContract":"{}"}}"#,
+                binding.chain_id,
+                binding.contract_hex()
+            ),
+        };
+        let [pk_x, pk_y] = self.public_key.to_circomlibjs().point;
+        format!(
+            r#"{{"domain":{},"message":"{}","publicKey":["{}","{}"],"signature":{}}}"#,
+            domain,
+            to_hex(&self.message),
+            pk_x,
+            pk_y,
+            self.signature.to_canonical_json()
+        )
+    }
+
+    /// Decode [`SignedMessage::to_canonical_json`].  Accepts only the
+    /// canonical form; the signature must also be canonical, and the public
+    /// key on the curve.  The signature is not verified.
+    pub fn from_canonical_json(json: &str) -> Option<Self> {
+        let fields: CanonicalEnvelope = serde_json::from_str(json).ok()?;
+        let domain = match fields.domain {
+            CanonicalDomain::None => SigningDomain::None,
+            CanonicalDomain::Context { context } => SigningDomain::Context(from_hex(&context)?),
+            CanonicalDomain::Chain {
+                chain_id,
+                verifying_contract,
+            } => SigningDomain::Chain(ChainBinding::from_hex_address(
+                chain_id.parse().ok()?,
+                &verifying_contract,
+            )?),
+        };
+        let signature = serde_json::to_string(&fields.signature).ok()?;
+        let signed = SignedMessage {
+            message: from_hex(&fields.message)?,
+            public_key: PublicKey::from_circomlibjs_point(&fields.public_key)?,
+            signature: Signature::from_canonical_json(&signature)?,
+            domain,
+        };
+        (signed.to_canonical_json() == json).then_some(signed)
+    }
+}
+
+/// Field layout of [`SignedMessage::to_canonical_json`].
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct CanonicalEnvelope {
+    domain: CanonicalDomain,
+    message: String,
+    public_key: [String; 2],
+    signature: Value,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", deny_unknown_fields)]
+enum CanonicalDomain {
+    None,
+    Context {
+        context: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Chain {
+        chain_id: String,
+        verifying_contract: String,
+    },
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn envelope_verifies_under_its_domain() {
+        let kp = KeyPair::generate();
+        let domains = [
+            SigningDomain::None,
+            SigningDomain::Context(b"app".to_vec()),
+            SigningDomain::Chain(ChainBinding::new(1, [0x11; 20])),
+        ];
+        for domain in domains {
+            let signed = SignedMessage::sign(&kp, b"msg", domain);
+            assert_eq!(signed.verify(), VerifyResult::Valid);
+        }
+    }
+
+    #[test]
+    fn canonical_json_roundtrip() {
+        let kp = KeyPair::generate();
+        let domains = [
+            SigningDomain::None,
+            SigningDomain::Context(b"app".to_vec()),
+            SigningDomain::Chain(ChainBinding::new(31337, [0xAB; 20])),
+        ];
+        for domain in domains {
+            let signed = SignedMessage::sign(&kp, b"over the wire", domain);
+            let json = signed.to_canonical_json();
+
+            let back = SignedMessage::from_canonical_json(&json).unwrap();
+            assert_eq!(back.domain, signed.domain);
+            assert_eq!(back.message, signed.message);
+            assert_eq!(back.to_canonical_json(), json);
+            assert_eq!(back.verify(), VerifyResult::Valid);
+        }
+    }
+
+    #[test]
+    fn canonical_json_rejects_other_forms() {
+        let kp = KeyPair::generate();
+        let json = SignedMessage::sign(&kp, b"msg", SigningDomain::None).to_canonical_json();
+
+        let value: Value = serde_json::from_str(&json).unwrap();
+        let pretty = serde_json::to_string_pretty(&value).unwrap();
+        assert!(SignedMessage::from_canonical_json(&pretty).is_none());
+
+        let upper = json.replace(&to_hex(b"msg"), &to_hex(b"msg").to_uppercase());
+        assert!(SignedMessage::from_canonical_json(&upper).is_none());
+
+        let extra = json.replacen('{', r#"{"a":"1","#, 1);
+        assert!(SignedMessage::from_canonical_json(&extra).is_none());
+    }
+
+    #[test]
+    fn envelope_with_wrong_domain_fails() {
+        let kp = KeyPair::generate();
+        let mut signed = SignedMessage::sign(&kp, b"msg", SigningDomain::Context(b"a".to_vec()));
+        signed.domain = SigningDomain::Context(b"b".to_vec());
+        assert_eq!(signed.verify(), VerifyResult::Invalid);
+    }
+}
@@ -7,13 +7,22 @@
 //
 // The Schnorr challenge is:
 //   e = Poseidon(R.x, PK.x, PK.y, msgHash)
+// squeezed through `transcript::Transcript` in its circuit-layout mode,
+// with Poseidon taken from a `profile::ChallengeProfile`.
 //
 // Message-to-field conversion uses SHA-256 → reduce mod p.
+//
+// Context-separated signing binds the message hash to a domain tag first:
+//   msgHash' = Poseidon(ctxTag, msgHash)
+// so a signature made under one context never verifies under another.
 
 use ark_bn254::Fr as Bn254Fr;
 use ark_ff::PrimeField;
 use light_poseidon::{Poseidon, PoseidonHasher};
 
+use crate::profile::{default_profile, ChallengeProfile};
+use crate::transcript::Transcript;
+
 /// Compute the Schnorr challenge hash:
 ///
 ///   e = Poseidon(r_x, pk_x, pk_y, message_hash)
@@ -26,12 +35,48 @@ pub fn schnorr_challenge(
     pk_y: &Bn254Fr,
     message_hash: &Bn254Fr,
 ) -> Bn254Fr {
-    let mut hasher =
-        Poseidon::<Bn254Fr>::new_circom(4).expect("Poseidon initialization failed for width 4");
+    schnorr_challenge_with_profile(default_profile(), r_x, pk_x, pk_y, message_hash)
+}
 
-    hasher
-        .hash(&[*r_x, *pk_x, *pk_y, *message_hash])
-        .expect("Poseidon hash failed")
+/// [`schnorr_challenge`] under an explicit challenge profile.
+pub fn schnorr_challenge_with_profile(
+    profile: &'static ChallengeProfile,
+    r_x: &Bn254Fr,
+    pk_x: &Bn254Fr,
+    pk_y: &Bn254Fr,
+    message_hash: &Bn254Fr,
+) -> Bn254Fr {
+    let mut transcript = Transcript::circom_with_profile(profile);
+    append_challenge_inputs(&mut transcript, r_x, pk_x, pk_y, message_hash);
+    transcript.challenge_field("e")
+}
+
+/// The transcript behind [`schnorr_challenge`], in circuit input order.
+///
+/// Uses [`Transcript::circom`] because `circuits/schnorr_verify.circom`
+/// fixes the layout to a bare `Poseidon(4)`.
+pub fn schnorr_transcript(
+    r_x: &Bn254Fr,
+    pk_x: &Bn254Fr,
+    pk_y: &Bn254Fr,
+    message_hash: &Bn254Fr,
+) -> Transcript {
+    let mut transcript = Transcript::circom();
+    append_challenge_inputs(&mut transcript, r_x, pk_x, pk_y, message_hash);
+    transcript
+}
+
+fn append_challenge_inputs(
+    transcript: &mut Transcript,
+    r_x: &Bn254Fr,
+    pk_x: &Bn254Fr,
+    pk_y: &Bn254Fr,
+    message_hash: &Bn254Fr,
+) {
+    transcript.append_field("R.x", r_x);
+    transcript.append_field("PK.x", pk_x);
+    transcript.append_field("PK.y", pk_y);
+    transcript.append_field("msgHash", message_hash);
 }
 
 /// Hash an arbitrary byte-string message to a BN254 field element.
@@ -46,6 +91,28 @@ pub fn hash_message_to_field(message: &[u8]) -> Bn254Fr {
     Bn254Fr::from_le_bytes_mod_order(&digest)
 }
 
+/// Map a signing context (domain string) to a BN254 field element.
+///
+/// Uses the same SHA-256 → mod p reduction as [`hash_message_to_field`];
+/// the result is what the circuit receives as `ctxTag`.
+pub fn context_tag(context: &[u8]) -> Bn254Fr {
+    hash_message_to_field(context)
+}
+
+/// Bind a message hash to a context tag:
+///
+///   msgHash' = Poseidon(ctx_tag, message_hash)
+///
+/// Compatible with circomlib's `Poseidon(2)`.
+pub fn bind_context(ctx_tag: &Bn254Fr, message_hash: &Bn254Fr) -> Bn254Fr {
+    let mut hasher =
+        Poseidon::<Bn254Fr>::new_circom(2).expect("Poseidon initialization failed for width 2");
+
+    hasher
+        .hash(&[*ctx_tag, *message_hash])
+        .expect("Poseidon hash failed")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,6 +130,18 @@ mod tests {
         assert_eq!(h1, h2, "Poseidon must be deterministic");
     }
 
+    #[test]
+    fn challenge_matches_raw_poseidon4() {
+        let inputs: Vec<Bn254Fr> = (1u64..=4).map(Bn254Fr::from).collect();
+        let mut hasher = Poseidon::<Bn254Fr>::new_circom(4).unwrap();
+        let expected = hasher.hash(&inputs).unwrap();
+        assert_eq!(
+            schnorr_challenge(&inputs[0], &inputs[1], &inputs[2], &inputs[3]),
+            expected,
+            "challenge must stay circomlib Poseidon(4)"
+        );
+    }
+
     #[test]
     fn poseidon_different_inputs_differ() {
         let a = Bn254Fr::one();
@@ -93,4 +172,13 @@ mod tests {
         let h = hash_message_to_field(b"test message");
         assert_ne!(h, Bn254Fr::from(0u64));
     }
+
+    #[test]
+    fn bound_hash_depends_on_context() {
+        let m = hash_message_to_field(b"msg");
+        let h1 = bind_context(&context_tag(b"app-a"), &m);
+        let h2 = bind_context(&context_tag(b"app-b"), &m);
+        assert_ne!(h1, h2);
+        assert_ne!(h1, m, "bound hash must differ from the raw message hash");
+    }
 }
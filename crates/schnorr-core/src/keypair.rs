@@ -3,7 +3,9 @@
 // Private key: random scalar sk ∈ Z_n  (BJJ subgroup order)
 // Public key:  PK = sk · G  (a BabyJubJub curve point)
 
-use crate::curve::{BjjPoint, BjjScalar};
+use serde::{Deserialize, Serialize};
+
+use crate::curve::{bn254_from_dec_str, bn254_to_dec_string, BjjPoint, BjjScalar};
 
 /// A Schnorr keypair over BabyJubJub.
 #[derive(Clone, Debug)]
@@ -39,11 +41,72 @@ impl KeyPair {
     }
 }
 
+/// A public key in the shapes circomlibjs expects.
+///
+/// `point` is the `[x, y]` pair of decimal strings that `babyJub` /
+/// `eddsa` functions take once mapped through `F.e(...)`; `packed` is the
+/// 32-byte `babyJub.packPoint` buffer (`Uint8Array.from(packed)` in JS).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircomlibjsPublicKey {
+    pub point: [String; 2],
+    pub packed: [u8; 32],
+}
+
 impl PublicKey {
     /// Get the (x, y) coordinates as BN254 field elements.
     pub fn coords(&self) -> (ark_bn254::Fr, ark_bn254::Fr) {
         self.point.coords()
     }
+
+    /// Export in the forms accepted by circomlibjs.
+    pub fn to_circomlibjs(&self) -> CircomlibjsPublicKey {
+        let (x, y) = self.coords();
+        CircomlibjsPublicKey {
+            point: [bn254_to_dec_string(&x), bn254_to_dec_string(&y)],
+            packed: self.point.pack(),
+        }
+    }
+
+    /// Import a key exported by [`PublicKey::to_circomlibjs`] or by circomlibjs.
+    ///
+    /// Returns `None` if the point does not have order n (see
+    /// [`BjjPoint::is_prime_order`]) or if `point` and `packed` disagree.
+    pub fn from_circomlibjs(key: &CircomlibjsPublicKey) -> Option<Self> {
+        let from_point = Self::from_circomlibjs_point(&key.point)?;
+        let from_packed = Self::from_circomlibjs_packed(&key.packed)?;
+        if from_point.point == from_packed.point {
+            Some(from_point)
+        } else {
+            None
+        }
+    }
+
+    /// Import from an `[x, y]` decimal string pair.  The point must have
+    /// order n.
+    pub fn from_circomlibjs_point(point: &[String; 2]) -> Option<Self> {
+        let point = BjjPoint {
+            x: bn254_from_dec_str(&point[0])?,
+            y: bn254_from_dec_str(&point[1])?,
+        };
+        if point.is_prime_order() {
+            Some(PublicKey { point })
+        } else {
+            None
+        }
+    }
+
+    /// Import from a 32-byte `babyJub.packPoint` buffer.  The point must
+    /// have order n.
+    pub fn from_circomlibjs_packed(packed: &[u8; 32]) -> Option<Self> {
+        BjjPoint::unpack(packed)
+            .filter(BjjPoint::is_prime_order)
+            .map(|point| PublicKey { point })
+    }
+
+    /// Stable key identifier: lowercase hex of the packed point.
+    pub fn fingerprint(&self) -> String {
+        self.point.pack().iter().map(|b| format!("{b:02x}")).collect()
+    }
 }
 
 #[cfg(test)]
@@ -72,4 +135,36 @@ mod tests {
         // Overwhelmingly likely to differ
         assert_ne!(kp1.pk.point, kp2.pk.point);
     }
+
+    #[test]
+    fn circomlibjs_roundtrip() {
+        let kp = KeyPair::generate();
+        let exported = kp.pk.to_circomlibjs();
+        let imported = PublicKey::from_circomlibjs(&exported).expect("import failed");
+        assert_eq!(imported.point, kp.pk.point);
+    }
+
+    #[test]
+    fn circomlibjs_rejects_mismatched_forms() {
+        let kp1 = KeyPair::generate();
+        let kp2 = KeyPair::generate();
+        let mut exported = kp1.pk.to_circomlibjs();
+        exported.packed = kp2.pk.to_circomlibjs().packed;
+        assert!(PublicKey::from_circomlibjs(&exported).is_none());
+    }
+
+    #[test]
+    fn circomlibjs_rejects_low_order_points() {
+        let identity = BjjPoint::identity();
+        let two_torsion = BjjPoint {
+            x: ark_bn254::Fr::from(0u64),
+            y: -ark_bn254::Fr::from(1u64),
+        };
+        for point in [identity, two_torsion] {
+            let (x, y) = point.coords();
+            let dec = [bn254_to_dec_string(&x), bn254_to_dec_string(&y)];
+            assert!(PublicKey::from_circomlibjs_point(&dec).is_none());
+            assert!(PublicKey::from_circomlibjs_packed(&point.pack()).is_none());
+        }
+    }
 }
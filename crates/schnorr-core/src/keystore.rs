@@ -0,0 +1,289 @@
+// crates/schnorr-core/src/keystore.rs
+//
+// JSON keystore for a single Schnorr keypair.
+//
+//   {
+//     "version": 1,
+//     "sk":  "<decimal, < n>",
+//     "pkX": "<decimal>",
+//     "pkY": "<decimal>",
+//     "metadata": {                       // optional
+//       "createdAt": 1700000000,          // Unix seconds, optional
+//       "expiresAt": 1731536000,          // Unix seconds, optional
+//       "usage": "witness-only"           // or "general" (default)
+//     }
+//   }
+//
+// The public key is stored for inspection only; on load it must match sk·G.
+// Metadata is enforced by `signer::KeystoreSigner` before every signature.
+
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::curve::{bn254_to_dec_string, BjjScalar};
+use crate::keypair::KeyPair;
+use crate::signer::{KeystoreSigner, SignError, SigningPurpose};
+
+/// Current keystore format version.
+pub const KEYSTORE_VERSION: u32 = 1;
+
+/// What a stored key may be used for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyUsage {
+    /// Only for producing circuit witnesses.
+    WitnessOnly,
+    /// Any signing.
+    #[default]
+    General,
+}
+
+impl KeyUsage {
+    /// Whether this usage policy allows signing for `purpose`.
+    pub fn permits(&self, purpose: SigningPurpose) -> bool {
+        match self {
+            KeyUsage::General => true,
+            KeyUsage::WitnessOnly => purpose == SigningPurpose::Witness,
+        }
+    }
+}
+
+/// Optional guardrails attached to a stored key.
+///
+/// Unknown fields are rejected: a misspelled `expiresAt` must not load as a
+/// key that never expires.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct KeyMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    #[serde(default)]
+    pub usage: KeyUsage,
+}
+
+impl KeyMetadata {
+    /// Check whether signing for `purpose` is allowed at time `now`
+    /// (Unix seconds).
+    pub fn check(&self, purpose: SigningPurpose, now: u64) -> Result<(), SignError> {
+        if let Some(created_at) = self.created_at {
+            if now < created_at {
+                return Err(SignError::NotYetValid { created_at, now });
+            }
+        }
+        if let Some(expires_at) = self.expires_at {
+            if now >= expires_at {
+                return Err(SignError::Expired { expires_at, now });
+            }
+        }
+        if !self.usage.permits(purpose) {
+            return Err(SignError::UsageDenied {
+                usage: self.usage,
+                purpose,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Errors loading a keystore file.
+#[derive(Debug)]
+pub enum KeystoreError {
+    Io(std::io::Error),
+    Format(String),
+    UnsupportedVersion(u32),
+    InvalidSecretKey,
+    /// Stored pkX/pkY do not match sk·G.
+    PublicKeyMismatch,
+}
+
+impl fmt::Display for KeystoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeystoreError::Io(e) => write!(f, "keystore I/O error: {e}"),
+            KeystoreError::Format(e) => write!(f, "malformed keystore: {e}"),
+            KeystoreError::UnsupportedVersion(v) => write!(f, "unsupported keystore version {v}"),
+            KeystoreError::InvalidSecretKey => write!(f, "keystore secret key is not a valid scalar"),
+            KeystoreError::PublicKeyMismatch => write!(f, "keystore public key does not match sk"),
+        }
+    }
+}
+
+impl std::error::Error for KeystoreError {}
+
+impl From<std::io::Error> for KeystoreError {
+    fn from(e: std::io::Error) -> Self {
+        KeystoreError::Io(e)
+    }
+}
+
+/// On-disk keystore representation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Keystore {
+    pub version: u32,
+    pub sk: String,
+    pub pk_x: String,
+    pub pk_y: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<KeyMetadata>,
+}
+
+impl Keystore {
+    /// Wrap a keypair for storage.
+    pub fn new(keypair: &KeyPair, metadata: Option<KeyMetadata>) -> Self {
+        let (pk_x, pk_y) = keypair.pk.coords();
+        Keystore {
+            version: KEYSTORE_VERSION,
+            sk: keypair.sk.to_dec_string(),
+            pk_x: bn254_to_dec_string(&pk_x),
+            pk_y: bn254_to_dec_string(&pk_y),
+            metadata,
+        }
+    }
+
+    /// Recover the keypair, checking the stored public key.  Crate-private:
+    /// outside callers sign through [`Keystore::signer`], which enforces the
+    /// metadata.
+    pub(crate) fn keypair(&self) -> Result<KeyPair, KeystoreError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(KeystoreError::UnsupportedVersion(self.version));
+        }
+        let sk = BjjScalar::from_dec_str(&self.sk).ok_or(KeystoreError::InvalidSecretKey)?;
+        let keypair = KeyPair::from_private_key(sk);
+        let (pk_x, pk_y) = keypair.pk.coords();
+        if bn254_to_dec_string(&pk_x) != self.pk_x || bn254_to_dec_string(&pk_y) != self.pk_y {
+            return Err(KeystoreError::PublicKeyMismatch);
+        }
+        Ok(keypair)
+    }
+
+    /// Build a signer that enforces this keystore's metadata.
+    ///
+    /// A keystore without metadata yields an unrestricted signer.
+    pub fn signer(&self) -> Result<KeystoreSigner, KeystoreError> {
+        let metadata = self.metadata.clone().unwrap_or_default();
+        Ok(KeystoreSigner::new(self.keypair()?, metadata))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, KeystoreError> {
+        serde_json::from_str(json).map_err(|e| KeystoreError::Format(e.to_string()))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("JSON serialization failed")
+    }
+
+    pub fn load(path: &Path) -> Result<Self, KeystoreError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Write the keystore.  On unix the file is owner-only (0600), since it
+    /// holds the plaintext secret key.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        use std::io::Write;
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+            options.mode(0o600);
+            let mut file = options.open(path)?;
+            // `mode` only applies on creation; tighten an existing file too.
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+            file.write_all(self.to_json().as_bytes())
+        }
+        #[cfg(not(unix))]
+        {
+            options.open(path)?.write_all(self.to_json().as_bytes())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_roundtrip_with_metadata() {
+        let kp = KeyPair::generate();
+        let metadata = KeyMetadata {
+            created_at: Some(100),
+            expires_at: Some(200),
+            usage: KeyUsage::WitnessOnly,
+        };
+        let ks = Keystore::new(&kp, Some(metadata));
+        let json = ks.to_json();
+        assert!(json.contains("\"witness-only\""));
+
+        let back = Keystore::from_json(&json).unwrap();
+        assert_eq!(back, ks);
+        assert_eq!(back.keypair().unwrap().pk.point, kp.pk.point);
+    }
+
+    #[test]
+    fn metadata_is_optional() {
+        let kp = KeyPair::generate();
+        let json = Keystore::new(&kp, None).to_json();
+        assert!(!json.contains("metadata"));
+        let signer = Keystore::from_json(&json).unwrap().signer().unwrap();
+        assert_eq!(signer.metadata(), &KeyMetadata::default());
+    }
+
+    #[test]
+    fn misspelled_metadata_rejected() {
+        let json = Keystore::new(&KeyPair::generate(), Some(KeyMetadata::default())).to_json();
+        for (good, bad) in [(r#""usage""#, r#""expiresAT""#), (r#""metadata""#, r#""metaData""#)] {
+            let misspelled = json.replace(good, bad);
+            assert_ne!(misspelled, json);
+            assert!(Keystore::from_json(&misspelled).is_err());
+        }
+    }
+
+    #[test]
+    fn tampered_public_key_rejected() {
+        let mut ks = Keystore::new(&KeyPair::generate(), None);
+        ks.pk_x = "1".to_string();
+        assert!(matches!(ks.keypair(), Err(KeystoreError::PublicKeyMismatch)));
+    }
+
+    #[test]
+    fn validity_window() {
+        let metadata = KeyMetadata {
+            created_at: Some(100),
+            expires_at: Some(200),
+            usage: KeyUsage::General,
+        };
+        assert!(metadata.check(SigningPurpose::General, 150).is_ok());
+        assert!(matches!(
+            metadata.check(SigningPurpose::General, 50),
+            Err(SignError::NotYetValid { .. })
+        ));
+        assert!(matches!(
+            metadata.check(SigningPurpose::General, 200),
+            Err(SignError::Expired { .. })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn saved_keystore_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path =
+            std::env::temp_dir().join(format!("schnorr-keystore-{}.json", std::process::id()));
+        std::fs::write(&path, "").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        Keystore::new(&KeyPair::generate(), None).save(&path).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(Keystore::load(&path).is_ok());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,15 +1,38 @@
+pub mod binding;
 pub mod curve;
+pub mod envelope;
 pub mod hash;
 pub mod keypair;
+pub mod keystore;
+pub mod nonce;
+pub mod profile;
+pub mod registry;
+pub mod scheme;
 pub mod sign;
+pub mod signer;
+pub mod transcript;
 pub mod verify;
 
+/// Crate version, recorded in proving manifests.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // Re-exports for convenience
+pub use binding::ChainBinding;
 pub use curve::{BjjPoint, BjjScalar};
-pub use hash::{hash_message_to_field, schnorr_challenge};
-pub use keypair::{KeyPair, PublicKey};
+pub use envelope::{SignedMessage, SigningDomain};
+pub use hash::{bind_context, context_tag, hash_message_to_field, schnorr_challenge};
+pub use keypair::{CircomlibjsPublicKey, KeyPair, PublicKey};
+pub use keystore::{KeyMetadata, KeyUsage, Keystore, KeystoreError};
+pub use nonce::NonceSource;
+pub use profile::ChallengeProfile;
+pub use registry::{RegistryError, SignerRegistry};
+pub use scheme::{Schnorr, SignatureScheme};
 pub use sign::Signature;
-pub use verify::{verify, VerifyResult};
+pub use signer::{KeystoreSigner, SchnorrSigner, SignError, SigningPurpose};
+pub use transcript::Transcript;
+pub use verify::{
+    verify, verify_with_chain_binding, verify_with_context, verify_with_context_tag, VerifyResult,
+};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,109 @@
+// crates/schnorr-core/src/nonce.rs
+//
+// Nonce sources for multi-party signing sessions.
+//
+// Production sessions draw nonces from OS randomness.  Integration tests and
+// cross-implementation conformance suites need sessions that replay
+// bit-for-bit, so a seeded mode derives every nonce from
+//
+//   k = SHA-512( "bjj-schnorr/seeded-nonce/v1"
+//                || len(seed)    || seed
+//                || len(session) || session
+//                || participant  || index ) mod n
+//
+// with lengths and integers encoded as 8- and 4-byte little-endian.
+// `participant` is the signer's position in the session and `index`
+// distinguishes multiple nonces per signer (e.g. MuSig2's two nonces).
+//
+// Seeded nonces are predictable to anyone holding the seed; never use them
+// with real keys.
+
+use ark_ed_on_bn254::Fr as BjjFr;
+use ark_ff::PrimeField;
+
+use crate::curve::BjjScalar;
+
+const SEEDED_NONCE_DOMAIN: &[u8] = b"bjj-schnorr/seeded-nonce/v1";
+
+/// Where a session's signing nonces come from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NonceSource {
+    /// Fresh OS randomness for every nonce.
+    Random,
+    /// Deterministic derivation from a fixed seed.  **Test fixtures only.**
+    Seeded(Vec<u8>),
+}
+
+impl NonceSource {
+    /// Seeded source for replayable fixtures.
+    pub fn seeded(seed: &[u8]) -> Self {
+        NonceSource::Seeded(seed.to_vec())
+    }
+
+    /// Produce the nonce for one participant's `index`-th nonce in a session.
+    ///
+    /// The coordinates are ignored by [`NonceSource::Random`].
+    pub fn nonce(&self, session_id: &[u8], participant: u32, index: u32) -> BjjScalar {
+        match self {
+            NonceSource::Random => BjjScalar::random(&mut ark_std::rand::rngs::OsRng),
+            NonceSource::Seeded(seed) => seeded_nonce(seed, session_id, participant, index),
+        }
+    }
+}
+
+/// Derive a replayable nonce (see module docs for the exact encoding).
+pub fn seeded_nonce(seed: &[u8], session_id: &[u8], participant: u32, index: u32) -> BjjScalar {
+    use sha2::{Digest, Sha512};
+
+    let mut hasher = Sha512::new();
+    hasher.update(SEEDED_NONCE_DOMAIN);
+    hasher.update((seed.len() as u64).to_le_bytes());
+    hasher.update(seed);
+    hasher.update((session_id.len() as u64).to_le_bytes());
+    hasher.update(session_id);
+    hasher.update(participant.to_le_bytes());
+    hasher.update(index.to_le_bytes());
+    let digest = hasher.finalize();
+
+    BjjScalar(BjjFr::from_le_bytes_mod_order(&digest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_nonces_replay() {
+        let a = NonceSource::seeded(b"fixture-seed");
+        let b = NonceSource::seeded(b"fixture-seed");
+        for participant in 0..3 {
+            for index in 0..2 {
+                assert_eq!(
+                    a.nonce(b"session-1", participant, index),
+                    b.nonce(b"session-1", participant, index)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn seeded_nonces_are_distinct_per_coordinate() {
+        let src = NonceSource::seeded(b"fixture-seed");
+        let base = src.nonce(b"session-1", 0, 0);
+        assert_ne!(base, src.nonce(b"session-2", 0, 0));
+        assert_ne!(base, src.nonce(b"session-1", 1, 0));
+        assert_ne!(base, src.nonce(b"session-1", 0, 1));
+        assert_ne!(base, NonceSource::seeded(b"other-seed").nonce(b"session-1", 0, 0));
+    }
+
+    #[test]
+    fn length_prefix_prevents_seed_session_ambiguity() {
+        assert_ne!(seeded_nonce(b"ab", b"c", 0, 0), seeded_nonce(b"a", b"bc", 0, 0));
+    }
+
+    #[test]
+    fn random_nonces_differ() {
+        let src = NonceSource::Random;
+        assert_ne!(src.nonce(b"s", 0, 0), src.nonce(b"s", 0, 0));
+    }
+}
@@ -0,0 +1,119 @@
+// crates/schnorr-core/src/profile.rs
+//
+// Registry of challenge-hash profiles.
+//
+// A profile names the Poseidon instance behind the Schnorr challenge
+//
+//   e = H(R.x, PK.x, PK.y, msgHash)
+//
+// and knows how to construct its hasher.  Signatures carry their profile,
+// and serialized forms store the profile id, so a verifier picks the right
+// challenge function from the data instead of assuming one.
+//
+// Only profiles with a circuit-compatible implementation are registered;
+// `lookup` returns `None` for anything else (e.g. "poseidon2-bn254-w4"
+// until a Poseidon2 hasher and circuit exist).
+
+use std::fmt;
+
+use ark_bn254::Fr as Bn254Fr;
+use light_poseidon::{Poseidon, PoseidonHasher};
+
+/// Builds a hasher for the given number of inputs.
+pub type HasherConstructor = fn(usize) -> Box<dyn PoseidonHasher<Bn254Fr>>;
+
+/// A named challenge-hash parameter set.
+pub struct ChallengeProfile {
+    /// Stable identifier stored in serialized signatures and manifests.
+    pub id: &'static str,
+    /// Field, hash family and challenge width, for humans.
+    pub description: &'static str,
+    new_hasher: HasherConstructor,
+}
+
+impl ChallengeProfile {
+    /// A fresh hasher for `width` inputs.
+    pub fn hasher(&self, width: usize) -> Box<dyn PoseidonHasher<Bn254Fr>> {
+        (self.new_hasher)(width)
+    }
+
+    /// Whether this is the profile `circuits/schnorr_verify.circom` checks.
+    pub fn is_default(&self) -> bool {
+        self.id == default_profile().id
+    }
+}
+
+impl fmt::Debug for ChallengeProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ChallengeProfile").field(&self.id).finish()
+    }
+}
+
+impl PartialEq for ChallengeProfile {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for ChallengeProfile {}
+
+/// circomlib `Poseidon(4)` over BN254, via light-poseidon.
+pub static CIRCOMLIB_POSEIDON_BN254_W4: ChallengeProfile = ChallengeProfile {
+    id: "circomlib-poseidon-bn254-w4",
+    description: "circomlib Poseidon over the BN254 scalar field, 4 challenge inputs",
+    new_hasher: circomlib_poseidon,
+};
+
+/// Unregistered non-default profile for exercising profile-specific paths.
+#[cfg(test)]
+pub(crate) static TEST_PROFILE: ChallengeProfile = ChallengeProfile {
+    id: "test-profile",
+    description: "circomlib Poseidon under a non-default id (tests only)",
+    new_hasher: circomlib_poseidon,
+};
+
+static PROFILES: &[&ChallengeProfile] = &[&CIRCOMLIB_POSEIDON_BN254_W4];
+
+fn circomlib_poseidon(width: usize) -> Box<dyn PoseidonHasher<Bn254Fr>> {
+    Box::new(Poseidon::<Bn254Fr>::new_circom(width).expect("Poseidon initialization failed"))
+}
+
+/// The profile used when none is specified.
+pub fn default_profile() -> &'static ChallengeProfile {
+    &CIRCOMLIB_POSEIDON_BN254_W4
+}
+
+/// Every registered profile.
+pub fn profiles() -> &'static [&'static ChallengeProfile] {
+    PROFILES
+}
+
+/// Resolve a profile id.  `None` if this build does not implement it.
+pub fn lookup(id: &str) -> Option<&'static ChallengeProfile> {
+    PROFILES.iter().copied().find(|p| p.id == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_known_and_unknown() {
+        assert_eq!(lookup("circomlib-poseidon-bn254-w4"), Some(default_profile()));
+        assert!(lookup("poseidon2-bn254-w4").is_none());
+    }
+
+    #[test]
+    fn circomlib_hasher_matches_light_poseidon() {
+        let inputs: Vec<Bn254Fr> = (1u64..=4).map(Bn254Fr::from).collect();
+        let expected = Poseidon::<Bn254Fr>::new_circom(4).unwrap().hash(&inputs).unwrap();
+        assert_eq!(default_profile().hasher(4).hash(&inputs).unwrap(), expected);
+    }
+
+    #[test]
+    fn ids_are_unique() {
+        for (i, p) in profiles().iter().enumerate() {
+            assert!(profiles()[i + 1..].iter().all(|q| q.id != p.id));
+        }
+    }
+}
@@ -0,0 +1,198 @@
+// crates/schnorr-core/src/registry.rs
+//
+// Concurrent map from key fingerprints to signers.
+//
+// Services holding many keys register each signer once and route signing
+// requests by `PublicKey::fingerprint`.  Lookups take a read lock and hand
+// out an `Arc`, so signing never runs while the map is locked.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::sign::Signature;
+use crate::signer::{SchnorrSigner, SignError, SigningPurpose};
+
+/// A signer that can be shared across threads.
+pub type SharedSigner = Arc<dyn SchnorrSigner + Send + Sync>;
+
+/// Why a registry signing request failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegistryError {
+    /// No signer is registered under this fingerprint.
+    UnknownKey(String),
+    /// The signer refused (expired key, usage policy, ...).
+    Sign(SignError),
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::UnknownKey(fp) => write!(f, "no signer registered for key {fp}"),
+            RegistryError::Sign(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+impl From<SignError> for RegistryError {
+    fn from(e: SignError) -> Self {
+        RegistryError::Sign(e)
+    }
+}
+
+/// Signers keyed by public-key fingerprint.
+#[derive(Default)]
+pub struct SignerRegistry {
+    signers: RwLock<HashMap<String, SharedSigner>>,
+}
+
+impl SignerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide registry.
+    pub fn global() -> &'static SignerRegistry {
+        static GLOBAL: OnceLock<SignerRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(SignerRegistry::new)
+    }
+
+    /// Register a signer under its public key's fingerprint, replacing any
+    /// signer already registered for that key.  Returns the fingerprint.
+    pub fn register(&self, signer: Box<dyn SchnorrSigner + Send + Sync>) -> String {
+        let fingerprint = signer.public_key().fingerprint();
+        self.write().insert(fingerprint.clone(), Arc::from(signer));
+        fingerprint
+    }
+
+    /// Remove a signer.  Returns it if it was registered.
+    pub fn remove(&self, fingerprint: &str) -> Option<SharedSigner> {
+        self.write().remove(fingerprint)
+    }
+
+    pub fn get(&self, fingerprint: &str) -> Option<SharedSigner> {
+        self.read().get(fingerprint).cloned()
+    }
+
+    pub fn contains(&self, fingerprint: &str) -> bool {
+        self.read().contains_key(fingerprint)
+    }
+
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Registered fingerprints, sorted.
+    pub fn fingerprints(&self) -> Vec<String> {
+        let mut fps: Vec<String> = self.read().keys().cloned().collect();
+        fps.sort();
+        fps
+    }
+
+    /// Sign with the signer registered under `fingerprint`.
+    pub fn sign(
+        &self,
+        fingerprint: &str,
+        message: &[u8],
+        purpose: SigningPurpose,
+    ) -> Result<Signature, RegistryError> {
+        let signer = self
+            .get(fingerprint)
+            .ok_or_else(|| RegistryError::UnknownKey(fingerprint.to_string()))?;
+        Ok(signer.sign(message, purpose)?)
+    }
+
+    // A panic while holding the lock cannot leave the map half-updated
+    // (every critical section is a single HashMap call), so poisoning is
+    // ignored.
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, SharedSigner>> {
+        self.signers.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, SharedSigner>> {
+        self.signers.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for SignerRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignerRegistry")
+            .field("fingerprints", &self.fingerprints())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keypair::KeyPair;
+    use crate::keystore::{KeyMetadata, KeyUsage};
+    use crate::signer::KeystoreSigner;
+    use crate::verify::{verify, VerifyResult};
+
+    #[test]
+    fn routes_by_fingerprint() {
+        let registry = SignerRegistry::new();
+        let a = KeyPair::generate();
+        let b = KeyPair::generate();
+        let fp_a = registry.register(Box::new(a.clone()));
+        let fp_b = registry.register(Box::new(b.clone()));
+        assert_eq!(registry.len(), 2);
+        assert_eq!(fp_a, a.pk.fingerprint());
+
+        let sig = registry.sign(&fp_b, b"msg", SigningPurpose::General).unwrap();
+        assert_eq!(verify(&sig, b"msg", &b.pk), VerifyResult::Valid);
+        assert_eq!(verify(&sig, b"msg", &a.pk), VerifyResult::Invalid);
+
+        assert!(registry.remove(&fp_a).is_some());
+        assert!(matches!(
+            registry.sign(&fp_a, b"msg", SigningPurpose::General),
+            Err(RegistryError::UnknownKey(fp)) if fp == fp_a
+        ));
+    }
+
+    #[test]
+    fn signer_policy_still_applies() {
+        let registry = SignerRegistry::new();
+        let metadata = KeyMetadata {
+            usage: KeyUsage::WitnessOnly,
+            ..KeyMetadata::default()
+        };
+        let fp = registry.register(Box::new(KeystoreSigner::new(KeyPair::generate(), metadata)));
+        assert!(registry.sign(&fp, b"msg", SigningPurpose::Witness).is_ok());
+        assert!(matches!(
+            registry.sign(&fp, b"msg", SigningPurpose::General),
+            Err(RegistryError::Sign(SignError::UsageDenied { .. }))
+        ));
+    }
+
+    #[test]
+    fn concurrent_signing() {
+        let registry = Arc::new(SignerRegistry::new());
+        let kp = KeyPair::generate();
+        let fp = registry.register(Box::new(kp.clone()));
+
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let registry = Arc::clone(&registry);
+                let fp = fp.clone();
+                std::thread::spawn(move || {
+                    let msg = format!("msg-{i}");
+                    let sig = registry.sign(&fp, msg.as_bytes(), SigningPurpose::General).unwrap();
+                    (msg, sig)
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            let (msg, sig) = handle.join().unwrap();
+            assert_eq!(verify(&sig, msg.as_bytes(), &kp.pk), VerifyResult::Valid);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+// crates/schnorr-core/src/scheme.rs
+//
+// A scheme-agnostic signing interface.
+//
+// Applications that support several signature schemes can be written once
+// against `SignatureScheme` and instantiated per scheme.  Each scheme is a
+// zero-sized marker type; `Schnorr` is the native scheme of this crate.
+
+use crate::keypair::{KeyPair, PublicKey};
+use crate::sign::Signature;
+use crate::verify::{verify, VerifyResult};
+
+/// Key generation, signing, verification and byte encodings for one scheme.
+pub trait SignatureScheme {
+    type KeyPair;
+    type PublicKey;
+    type Signature;
+
+    /// Stable identifier, e.g. for tagging serialized data.
+    const NAME: &'static str;
+
+    fn keygen() -> Self::KeyPair;
+
+    fn public_key(keypair: &Self::KeyPair) -> &Self::PublicKey;
+
+    fn sign(keypair: &Self::KeyPair, message: &[u8]) -> Self::Signature;
+
+    fn verify(public_key: &Self::PublicKey, message: &[u8], sig: &Self::Signature) -> VerifyResult;
+
+    fn public_key_to_bytes(public_key: &Self::PublicKey) -> Vec<u8>;
+
+    fn public_key_from_bytes(bytes: &[u8]) -> Option<Self::PublicKey>;
+
+    fn signature_to_bytes(sig: &Self::Signature) -> Vec<u8>;
+
+    fn signature_from_bytes(bytes: &[u8]) -> Option<Self::Signature>;
+}
+
+/// Native Schnorr over BabyJubJub with a Poseidon challenge.
+///
+/// Public keys encode as the 32-byte circomlibjs packed point; signatures as
+/// [`Signature::to_tagged_bytes`], so the challenge profile survives a round
+/// trip.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Schnorr;
+
+impl SignatureScheme for Schnorr {
+    type KeyPair = KeyPair;
+    type PublicKey = PublicKey;
+    type Signature = Signature;
+
+    const NAME: &'static str = "schnorr-bjj-poseidon";
+
+    fn keygen() -> KeyPair {
+        KeyPair::generate()
+    }
+
+    fn public_key(keypair: &KeyPair) -> &PublicKey {
+        &keypair.pk
+    }
+
+    fn sign(keypair: &KeyPair, message: &[u8]) -> Signature {
+        Signature::sign(keypair, message)
+    }
+
+    fn verify(public_key: &PublicKey, message: &[u8], sig: &Signature) -> VerifyResult {
+        verify(sig, message, public_key)
+    }
+
+    fn public_key_to_bytes(public_key: &PublicKey) -> Vec<u8> {
+        public_key.point.pack().to_vec()
+    }
+
+    fn public_key_from_bytes(bytes: &[u8]) -> Option<PublicKey> {
+        PublicKey::from_circomlibjs_packed(bytes.try_into().ok()?)
+    }
+
+    fn signature_to_bytes(sig: &Signature) -> Vec<u8> {
+        sig.to_tagged_bytes()
+    }
+
+    fn signature_from_bytes(bytes: &[u8]) -> Option<Signature> {
+        Signature::from_tagged_bytes(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Exercise a scheme purely through the trait.
+    fn roundtrip<S: SignatureScheme>() {
+        let kp = S::keygen();
+        let msg = b"generic message";
+        let sig = S::sign(&kp, msg);
+
+        let pk = S::public_key_from_bytes(&S::public_key_to_bytes(S::public_key(&kp))).unwrap();
+        let sig = S::signature_from_bytes(&S::signature_to_bytes(&sig)).unwrap();
+
+        assert_eq!(S::verify(&pk, msg, &sig), VerifyResult::Valid);
+        assert_eq!(S::verify(&pk, b"other", &sig), VerifyResult::Invalid);
+    }
+
+    #[test]
+    fn schnorr_through_trait() {
+        roundtrip::<Schnorr>();
+    }
+
+    #[test]
+    fn wrong_length_rejected() {
+        assert!(Schnorr::public_key_from_bytes(&[0u8; 31]).is_none());
+
+        let sig = Schnorr::sign(&Schnorr::keygen(), b"msg");
+        let tagged = Schnorr::signature_to_bytes(&sig);
+        assert!(Schnorr::signature_from_bytes(&tagged[..tagged.len() - 1]).is_none());
+        assert!(Schnorr::signature_from_bytes(&sig.to_bytes()).is_none());
+    }
+
+    #[test]
+    fn signature_bytes_carry_profile() {
+        let sig = Schnorr::sign(&Schnorr::keygen(), b"msg");
+        let back = Schnorr::signature_from_bytes(&Schnorr::signature_to_bytes(&sig)).unwrap();
+        assert_eq!(back.profile, sig.profile);
+    }
+
+    #[test]
+    fn identity_public_key_rejected() {
+        let identity = crate::curve::BjjPoint::identity().pack();
+        assert!(Schnorr::public_key_from_bytes(&identity).is_none());
+    }
+}
@@ -1,7 +1,7 @@
 // Schnorr signing over BabyJubJub.
 //
 // Signing a message m with private key sk:
-//   1. k = deterministic_nonce(sk, m)    — prevents nonce reuse
+//   1. k = deterministic_nonce(sk, H(m))  — prevents nonce reuse
 //   2. R = k · G
 //   3. e = Poseidon(R.x, PK.x, PK.y, H(m))   — challenge in F_p
 //   4. e_n = e mod n                           — reduce to BJJ scalar field
@@ -12,14 +12,32 @@
 // what Poseidon outputs and what the circom circuit operates in.
 // The response `s` lives in Z_n (BJJ scalar field) because it involves
 // curve-scalar arithmetic.
+//
+// Context-separated signing replaces H(m) with Poseidon(ctxTag, H(m)) in
+// step 3.  The nonce is always derived from the msgHash that actually enters
+// the challenge (fixed-length, domain-prefixed), so two signatures share k
+// only if they share the challenge input — k reuse across contexts, or
+// between a context signature and a plain one, cannot happen.
+//
+// The challenge hash is taken from the signature's `ChallengeProfile`
+// (default: circomlib Poseidon).  Non-default profiles derive the nonce under
+// a separate label that also covers the profile id: one k with two different
+// challenges reveals sk.
 
 use ark_bn254::Fr as Bn254Fr;
 use ark_ed_on_bn254::Fr as BjjFr;
 use ark_ff::{BigInteger, PrimeField};
+use serde::Deserialize;
 
-use crate::curve::{bn254_to_bjj_scalar, BjjPoint, BjjScalar};
-use crate::hash::{hash_message_to_field, schnorr_challenge};
+use crate::binding::ChainBinding;
+use crate::curve::{
+    bn254_from_dec_str, bn254_to_bjj_scalar, bn254_to_dec_string, BjjPoint, BjjScalar,
+};
+use crate::hash::{
+    bind_context, context_tag, hash_message_to_field, schnorr_challenge_with_profile,
+};
 use crate::keypair::KeyPair;
+use crate::profile::{default_profile, lookup, ChallengeProfile};
 
 /// A Schnorr signature (s, e) over BabyJubJub.
 #[derive(Clone, Debug)]
@@ -30,30 +48,172 @@ pub struct Signature {
     pub e: Bn254Fr,
     /// Commitment point R = k · G (stored for convenience / debugging).
     pub r: BjjPoint,
+    /// Challenge hash the signature was made (and must be verified) with.
+    pub profile: &'static ChallengeProfile,
 }
 
 impl Signature {
     /// Sign a message with the given keypair (deterministic nonce).
     pub fn sign(keypair: &KeyPair, message: &[u8]) -> Self {
-        let k = deterministic_nonce(&keypair.sk, message);
-        Self::sign_with_nonce(keypair, message, &k)
+        let msg_hash = hash_message_to_field(message);
+        let k = deterministic_nonce(&keypair.sk, &msg_hash);
+        Self::sign_hashed(keypair, default_profile(), &msg_hash, &k)
     }
 
     /// Sign with an explicit nonce.  **Only for testing** — reusing a nonce
     /// across two messages leaks the private key.
     pub fn sign_with_nonce(keypair: &KeyPair, message: &[u8], k: &BjjScalar) -> Self {
+        let msg_hash = hash_message_to_field(message);
+        Self::sign_hashed(keypair, default_profile(), &msg_hash, k)
+    }
+
+    /// Sign a message with the challenge computed under `profile`.
+    pub fn sign_with_profile(
+        keypair: &KeyPair,
+        profile: &'static ChallengeProfile,
+        message: &[u8],
+    ) -> Self {
+        if profile.is_default() {
+            return Self::sign(keypair, message);
+        }
+        let msg_hash = hash_message_to_field(message);
+        let k = profile_nonce(&keypair.sk, profile, &msg_hash);
+        Self::sign_hashed(keypair, profile, &msg_hash, &k)
+    }
+
+    /// Sign a message under a signing context (domain string).
+    ///
+    /// The signature only verifies with [`crate::verify::verify_with_context`]
+    /// and the same context.
+    pub fn sign_with_context(keypair: &KeyPair, context: &[u8], message: &[u8]) -> Self {
+        Self::sign_with_context_tag(keypair, &context_tag(context), message)
+    }
+
+    /// Sign a message bound to one deployment (chain id + verifier contract).
+    ///
+    /// The signature only verifies with
+    /// [`crate::verify::verify_with_chain_binding`] and the same binding.
+    pub fn sign_with_chain_binding(
+        keypair: &KeyPair,
+        binding: &ChainBinding,
+        message: &[u8],
+    ) -> Self {
+        Self::sign_with_context_tag(keypair, &binding.context_tag(), message)
+    }
+
+    /// Sign a message under an already-derived context tag.
+    pub fn sign_with_context_tag(keypair: &KeyPair, ctx_tag: &Bn254Fr, message: &[u8]) -> Self {
+        let msg_hash = bind_context(ctx_tag, &hash_message_to_field(message));
+        let k = deterministic_nonce(&keypair.sk, &msg_hash);
+        Self::sign_hashed(keypair, default_profile(), &msg_hash, &k)
+    }
+
+    /// Fixed 96-byte encoding: s (32 LE) || e (32 LE) || packed R (32).
+    pub fn to_bytes(&self) -> [u8; 96] {
+        let mut out = [0u8; 96];
+        let s_bytes = self.s.0.into_bigint().to_bytes_le();
+        let e_bytes = self.e.into_bigint().to_bytes_le();
+        out[..s_bytes.len()].copy_from_slice(&s_bytes);
+        out[32..32 + e_bytes.len()].copy_from_slice(&e_bytes);
+        out[64..].copy_from_slice(&self.r.pack());
+        out
+    }
+
+    /// Decode [`Signature::to_bytes`].  Rejects non-canonical s or e and
+    /// an R that does not have order n.  The profile is the default one.
+    pub fn from_bytes(bytes: &[u8; 96]) -> Option<Self> {
+        let s = canonical_from_le::<BjjFr>(&bytes[..32])?;
+        let e = canonical_from_le::<Bn254Fr>(&bytes[32..64])?;
+        let r_packed: [u8; 32] = bytes[64..].try_into().expect("slice is 32 bytes");
+        let r = BjjPoint::unpack(&r_packed).filter(BjjPoint::is_prime_order)?;
+        Some(Signature {
+            s: BjjScalar(s),
+            e,
+            r,
+            profile: default_profile(),
+        })
+    }
+
+    /// Self-describing encoding: id length (1 byte) || profile id || [`Signature::to_bytes`].
+    pub fn to_tagged_bytes(&self) -> Vec<u8> {
+        let id = self.profile.id.as_bytes();
+        let mut out = Vec::with_capacity(1 + id.len() + 96);
+        out.push(u8::try_from(id.len()).expect("profile id longer than 255 bytes"));
+        out.extend_from_slice(id);
+        out.extend_from_slice(&self.to_bytes());
+        out
+    }
+
+    /// Decode [`Signature::to_tagged_bytes`].  `None` if the profile is not
+    /// registered in this build.
+    pub fn from_tagged_bytes(bytes: &[u8]) -> Option<Self> {
+        let (&id_len, rest) = bytes.split_first()?;
+        let (id, sig) = rest.split_at_checked(id_len as usize)?;
+        let profile = lookup(std::str::from_utf8(id).ok()?)?;
+        let mut decoded = Self::from_bytes(sig.try_into().ok()?)?;
+        decoded.profile = profile;
+        Some(decoded)
+    }
+
+    /// The one canonical JSON form of the signature:
+    ///
+    ///   {"e":"…","profile":"…","rX":"…","rY":"…","s":"…"}
+    ///
+    /// Keys in sorted order, no whitespace, values as canonical decimal
+    /// strings (as in the witness input), so equal signatures always
+    /// serialize to identical bytes.
+    pub fn to_canonical_json(&self) -> String {
+        let (r_x, r_y) = self.r.coords();
+        format!(
+            r#"{{"e":"{}","profile":"{}","rX":"{}","rY":"{}","s":"{}"}}"#,
+            bn254_to_dec_string(&self.e),
+            self.profile.id,
+            bn254_to_dec_string(&r_x),
+            bn254_to_dec_string(&r_y),
+            self.s.to_dec_string()
+        )
+    }
+
+    /// Decode [`Signature::to_canonical_json`].
+    ///
+    /// Accepts only the canonical form: any other key order, whitespace,
+    /// extra key, leading zero or out-of-range value is rejected, as are an
+    /// unregistered profile and an R that does not have order n.
+    pub fn from_canonical_json(json: &str) -> Option<Self> {
+        let fields: CanonicalSignature = serde_json::from_str(json).ok()?;
+        let r = BjjPoint {
+            x: bn254_from_dec_str(&fields.r_x)?,
+            y: bn254_from_dec_str(&fields.r_y)?,
+        };
+        if !r.is_prime_order() {
+            return None;
+        }
+        let sig = Signature {
+            s: BjjScalar::from_dec_str(&fields.s)?,
+            e: bn254_from_dec_str(&fields.e)?,
+            r,
+            profile: lookup(&fields.profile)?,
+        };
+        (sig.to_canonical_json() == json).then_some(sig)
+    }
+
+    /// Core signing routine over a message hash that is already in F_p.
+    fn sign_hashed(
+        keypair: &KeyPair,
+        profile: &'static ChallengeProfile,
+        msg_hash: &Bn254Fr,
+        k: &BjjScalar,
+    ) -> Self {
         let g = BjjPoint::generator();
 
         // R = k · G
         let r = g.scalar_mul(k);
         let (r_x, _r_y) = r.coords();
 
-        // Message hash
-        let msg_hash = hash_message_to_field(message);
         let (pk_x, pk_y) = keypair.pk.coords();
 
         // Challenge: e = Poseidon(R.x, PK.x, PK.y, msgHash)  ∈ F_p
-        let e: Bn254Fr = schnorr_challenge(&r_x, &pk_x, &pk_y, &msg_hash);
+        let e: Bn254Fr = schnorr_challenge_with_profile(profile, &r_x, &pk_x, &pk_y, msg_hash);
 
         // Reduce e to BJJ scalar field: e_n = e mod n
         let e_n: BjjScalar = bn254_to_bjj_scalar(&e);
@@ -61,22 +221,81 @@ impl Signature {
         // s = k − e_n · sk  (mod n)
         let s = BjjScalar(k.0 - e_n.0 * keypair.sk.0);
 
-        Signature { s, e, r }
+        Signature { s, e, r, profile }
     }
 }
-fn deterministic_nonce(sk: &BjjScalar, message: &[u8]) -> BjjScalar {
+/// Field layout of [`Signature::to_canonical_json`].
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct CanonicalSignature {
+    e: String,
+    profile: String,
+    r_x: String,
+    r_y: String,
+    s: String,
+}
+
+/// Domain prefix of [`deterministic_nonce`].
+const NONCE_DOMAIN: &[u8] = b"bjj-schnorr/nonce/v1";
+
+/// Nonce bound to the challenge input:
+/// SHA-512(NONCE_DOMAIN || sk (32 LE) || msgHash (32 LE)) mod n.
+///
+/// `msg_hash` must be the value hashed into the challenge (after any
+/// context binding), so equal nonces imply equal challenges.
+fn deterministic_nonce(sk: &BjjScalar, msg_hash: &Bn254Fr) -> BjjScalar {
     use sha2::{Digest, Sha512};
 
-    let sk_bytes = sk.0.into_bigint().to_bytes_le();
     let mut hasher = Sha512::new();
-    hasher.update(&sk_bytes);
-    hasher.update(message);
+    hasher.update(NONCE_DOMAIN);
+    hasher.update(le_bytes_32(&sk.0));
+    hasher.update(le_bytes_32(msg_hash));
     let digest = hasher.finalize();
 
     // Reduce 512-bit hash mod n → near-uniform scalar in Z_n
     BjjScalar(BjjFr::from_le_bytes_mod_order(&digest))
 }
 
+/// Domain prefix of [`profile_nonce`].
+const PROFILE_NONCE_DOMAIN: &[u8] = b"bjj-schnorr/profile-nonce/v1";
+
+/// Nonce for a non-default challenge profile:
+/// SHA-512(PROFILE_NONCE_DOMAIN || len(id) || id || sk (32 LE) || msgHash (32 LE)) mod n.
+fn profile_nonce(sk: &BjjScalar, profile: &ChallengeProfile, msg_hash: &Bn254Fr) -> BjjScalar {
+    use sha2::{Digest, Sha512};
+
+    let id = profile.id.as_bytes();
+    let mut hasher = Sha512::new();
+    hasher.update(PROFILE_NONCE_DOMAIN);
+    hasher.update([u8::try_from(id.len()).expect("profile id longer than 255 bytes")]);
+    hasher.update(id);
+    hasher.update(le_bytes_32(&sk.0));
+    hasher.update(le_bytes_32(msg_hash));
+    let digest = hasher.finalize();
+
+    BjjScalar(BjjFr::from_le_bytes_mod_order(&digest))
+}
+
+/// Fixed-width 32-byte little-endian encoding of a field element.
+fn le_bytes_32<F: PrimeField>(f: &F) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let bytes = f.into_bigint().to_bytes_le();
+    out[..bytes.len()].copy_from_slice(&bytes);
+    out
+}
+
+/// Parse little-endian bytes as a field element, rejecting values >= modulus.
+fn canonical_from_le<F: PrimeField>(bytes: &[u8]) -> Option<F> {
+    let f = F::from_le_bytes_mod_order(bytes);
+    let mut round_trip = f.into_bigint().to_bytes_le();
+    round_trip.resize(bytes.len(), 0);
+    if round_trip == bytes {
+        Some(f)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,4 +327,147 @@ mod tests {
         // verification would fail — let's at least check it's not identity.
         assert!(!sig.r.is_zero());
     }
+
+    #[test]
+    fn context_changes_nonce_and_challenge() {
+        let kp = KeyPair::generate();
+        let sig1 = Signature::sign_with_context(&kp, b"app-a", b"hello");
+        let sig2 = Signature::sign_with_context(&kp, b"app-b", b"hello");
+        let plain = Signature::sign(&kp, b"hello");
+        assert_ne!(sig1.r, sig2.r, "contexts must not share a nonce");
+        assert_ne!(sig1.e, plain.e);
+    }
+
+    #[test]
+    fn bound_nonce_does_not_collide_with_plain_signing() {
+        // The pre-fix nonce was SHA-512(sk || tag || m) for context signing
+        // and SHA-512(sk || m') for plain signing, so a plain signature on
+        // m' = tag || m reused k under a different challenge and revealed sk.
+        use crate::binding::ChainBinding;
+
+        let kp = KeyPair::generate();
+        let binding = ChainBinding::new(1, [0x11u8; 20]);
+        let cases = [
+            (context_tag(b"app"), Signature::sign_with_context(&kp, b"app", b"msg")),
+            (binding.context_tag(), Signature::sign_with_chain_binding(&kp, &binding, b"msg")),
+        ];
+        for (tag, bound_sig) in cases {
+            let mut crafted = le_bytes_32(&tag).to_vec();
+            crafted.extend_from_slice(b"msg");
+            let plain_sig = Signature::sign(&kp, &crafted);
+
+            assert_ne!(bound_sig.r, plain_sig.r, "different challenges must not share a nonce");
+        }
+    }
+
+    #[test]
+    fn profile_nonce_differs_from_context_nonce() {
+        // Previously a non-default profile keyed the nonce on
+        // context_tag(profile.id), i.e. exactly sign_with_context(profile.id, m).
+        use crate::profile::TEST_PROFILE;
+        use crate::verify::{verify, VerifyResult};
+
+        let kp = KeyPair::generate();
+        let sig = Signature::sign_with_profile(&kp, &TEST_PROFILE, b"msg");
+        let ctx_sig = Signature::sign_with_context(&kp, TEST_PROFILE.id.as_bytes(), b"msg");
+        let plain_sig = Signature::sign(&kp, b"msg");
+        assert_ne!(sig.r, ctx_sig.r);
+        assert_ne!(sig.r, plain_sig.r);
+        assert_eq!(verify(&sig, b"msg", &kp.pk), VerifyResult::Valid);
+    }
+
+    #[test]
+    fn bytes_roundtrip() {
+        let kp = KeyPair::generate();
+        let sig = Signature::sign(&kp, b"bytes");
+        let back = Signature::from_bytes(&sig.to_bytes()).unwrap();
+        assert_eq!(back.s, sig.s);
+        assert_eq!(back.e, sig.e);
+        assert_eq!(back.r, sig.r);
+    }
+
+    #[test]
+    fn from_bytes_rejects_non_canonical_scalar() {
+        let kp = KeyPair::generate();
+        let mut bytes = Signature::sign(&kp, b"bytes").to_bytes();
+        bytes[..32].copy_from_slice(&[0xFF; 32]);
+        assert!(Signature::from_bytes(&bytes).is_none());
+    }
+
+    #[test]
+    fn decoders_reject_identity_commitment() {
+        let kp = KeyPair::generate();
+        let sig = Signature::sign(&kp, b"bytes");
+
+        let mut bytes = sig.to_bytes();
+        bytes[64..].copy_from_slice(&BjjPoint::identity().pack());
+        assert!(Signature::from_bytes(&bytes).is_none());
+
+        let (r_x, r_y) = sig.r.coords();
+        let json = sig
+            .to_canonical_json()
+            .replace(&bn254_to_dec_string(&r_x), "0")
+            .replace(&bn254_to_dec_string(&r_y), "1");
+        assert!(Signature::from_canonical_json(&json).is_none());
+    }
+
+    #[test]
+    fn tagged_bytes_carry_profile() {
+        let kp = KeyPair::generate();
+        let sig = Signature::sign(&kp, b"bytes");
+        let tagged = sig.to_tagged_bytes();
+        let back = Signature::from_tagged_bytes(&tagged).unwrap();
+        assert_eq!(back.profile, sig.profile);
+        assert_eq!(back.e, sig.e);
+
+        let id = b"poseidon2-bn254-w4";
+        let mut unknown = vec![id.len() as u8];
+        unknown.extend_from_slice(id);
+        unknown.extend_from_slice(&sig.to_bytes());
+        assert!(Signature::from_tagged_bytes(&unknown).is_none());
+        assert!(Signature::from_tagged_bytes(&tagged[..tagged.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn canonical_json_roundtrip() {
+        let kp = KeyPair::from_private_key(BjjScalar(BjjFr::from(7u64)));
+        let sig = Signature::sign(&kp, b"canonical");
+        let json = sig.to_canonical_json();
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"e":"16796506948764614532380235453875422747760608716071238002402002975742912138192","#,
+                r#""profile":"circomlib-poseidon-bn254-w4","#,
+                r#""rX":"19349368814128681558408452140269919609136753918112322544888311993075288568651","#,
+                r#""rY":"823139090485071498132981813796260826895515167142515186151020501095166719601","#,
+                r#""s":"1307914257648470163737675700592958262405108315842990234395947506956901441359"}"#
+            ),
+            "canonical JSON must stay byte-stable"
+        );
+
+        let back = Signature::from_canonical_json(&json).unwrap();
+        assert_eq!(back.to_canonical_json(), json);
+        assert_eq!(back.s, sig.s);
+        assert_eq!(back.r, sig.r);
+    }
+
+    #[test]
+    fn canonical_json_rejects_other_forms() {
+        let kp = KeyPair::generate();
+        let json = Signature::sign(&kp, b"canonical").to_canonical_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        let pretty = serde_json::to_string_pretty(&value).unwrap();
+        assert!(Signature::from_canonical_json(&pretty).is_none());
+
+        let s = value["s"].as_str().unwrap();
+        let padded = json.replace(&format!(r#""s":"{s}""#), &format!(r#""s":"0{s}""#));
+        assert!(Signature::from_canonical_json(&padded).is_none());
+
+        let extra = json.replacen('{', r#"{"a":"1","#, 1);
+        assert!(Signature::from_canonical_json(&extra).is_none());
+
+        let unknown = json.replace("circomlib-poseidon-bn254-w4", "poseidon2-bn254-w4");
+        assert!(Signature::from_canonical_json(&unknown).is_none());
+    }
 }
@@ -0,0 +1,205 @@
+// crates/schnorr-core/src/signer.rs
+//
+// Signing behind a trait, so callers can hold either a bare `KeyPair` or a
+// key loaded from a keystore whose metadata restricts how it may be used.
+//
+// Every signing call names its purpose; a policy-carrying signer checks the
+// purpose and the key's validity window before touching the secret.
+
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ark_bn254::Fr as Bn254Fr;
+
+use crate::binding::ChainBinding;
+use crate::hash::context_tag;
+use crate::keypair::{KeyPair, PublicKey};
+use crate::keystore::{KeyMetadata, KeyUsage};
+use crate::sign::Signature;
+
+/// What a signature is being produced for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SigningPurpose {
+    /// Feeding a circuit witness (`schnorr-witness`).
+    Witness,
+    /// Any other signature.
+    General,
+}
+
+/// Why a signer refused to sign.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignError {
+    /// The key's `expiresAt` has passed.
+    Expired { expires_at: u64, now: u64 },
+    /// The key's `createdAt` lies in the future.
+    NotYetValid { created_at: u64, now: u64 },
+    /// The key's usage policy does not allow this purpose.
+    UsageDenied { usage: KeyUsage, purpose: SigningPurpose },
+}
+
+impl fmt::Display for SignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignError::Expired { expires_at, now } => {
+                write!(f, "key expired at {expires_at} (now {now})")
+            }
+            SignError::NotYetValid { created_at, now } => {
+                write!(f, "key not valid before {created_at} (now {now})")
+            }
+            SignError::UsageDenied { usage, purpose } => {
+                write!(f, "key usage {usage:?} does not permit {purpose:?} signing")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SignError {}
+
+/// Something that can produce Schnorr signatures for one public key.
+pub trait SchnorrSigner {
+    /// The public key signatures verify against.
+    fn public_key(&self) -> &PublicKey;
+
+    /// Sign a message (see [`Signature::sign`]).
+    fn sign(&self, message: &[u8], purpose: SigningPurpose) -> Result<Signature, SignError>;
+
+    /// Sign a message under a context tag (see [`Signature::sign_with_context_tag`]).
+    fn sign_with_context_tag(
+        &self,
+        ctx_tag: &Bn254Fr,
+        message: &[u8],
+        purpose: SigningPurpose,
+    ) -> Result<Signature, SignError>;
+
+    /// Sign a message under a context string (see [`Signature::sign_with_context`]).
+    fn sign_with_context(
+        &self,
+        context: &[u8],
+        message: &[u8],
+        purpose: SigningPurpose,
+    ) -> Result<Signature, SignError> {
+        self.sign_with_context_tag(&context_tag(context), message, purpose)
+    }
+
+    /// Sign a message bound to a deployment (see [`Signature::sign_with_chain_binding`]).
+    fn sign_with_chain_binding(
+        &self,
+        binding: &ChainBinding,
+        message: &[u8],
+        purpose: SigningPurpose,
+    ) -> Result<Signature, SignError> {
+        self.sign_with_context_tag(&binding.context_tag(), message, purpose)
+    }
+}
+
+/// A bare keypair carries no policy and signs for any purpose.
+impl SchnorrSigner for KeyPair {
+    fn public_key(&self) -> &PublicKey {
+        &self.pk
+    }
+
+    fn sign(&self, message: &[u8], _purpose: SigningPurpose) -> Result<Signature, SignError> {
+        Ok(Signature::sign(self, message))
+    }
+
+    fn sign_with_context_tag(
+        &self,
+        ctx_tag: &Bn254Fr,
+        message: &[u8],
+        _purpose: SigningPurpose,
+    ) -> Result<Signature, SignError> {
+        Ok(Signature::sign_with_context_tag(self, ctx_tag, message))
+    }
+}
+
+/// A keypair plus the keystore metadata that governs it.
+#[derive(Clone, Debug)]
+pub struct KeystoreSigner {
+    keypair: KeyPair,
+    metadata: KeyMetadata,
+}
+
+impl KeystoreSigner {
+    pub fn new(keypair: KeyPair, metadata: KeyMetadata) -> Self {
+        KeystoreSigner { keypair, metadata }
+    }
+
+    pub fn metadata(&self) -> &KeyMetadata {
+        &self.metadata
+    }
+
+    fn authorize(&self, purpose: SigningPurpose) -> Result<(), SignError> {
+        self.metadata.check(purpose, unix_now())
+    }
+}
+
+impl SchnorrSigner for KeystoreSigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.keypair.pk
+    }
+
+    fn sign(&self, message: &[u8], purpose: SigningPurpose) -> Result<Signature, SignError> {
+        self.authorize(purpose)?;
+        Ok(Signature::sign(&self.keypair, message))
+    }
+
+    fn sign_with_context_tag(
+        &self,
+        ctx_tag: &Bn254Fr,
+        message: &[u8],
+        purpose: SigningPurpose,
+    ) -> Result<Signature, SignError> {
+        self.authorize(purpose)?;
+        Ok(Signature::sign_with_context_tag(&self.keypair, ctx_tag, message))
+    }
+}
+
+/// Current time in seconds since the Unix epoch.
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock before Unix epoch")
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::{verify, VerifyResult};
+
+    #[test]
+    fn keypair_signer_matches_direct_signing() {
+        let kp = KeyPair::generate();
+        let sig = SchnorrSigner::sign(&kp, b"msg", SigningPurpose::General).unwrap();
+        assert_eq!(sig.e, Signature::sign(&kp, b"msg").e);
+    }
+
+    #[test]
+    fn witness_only_key_refuses_general_signing() {
+        let metadata = KeyMetadata {
+            usage: KeyUsage::WitnessOnly,
+            ..KeyMetadata::default()
+        };
+        let signer = KeystoreSigner::new(KeyPair::generate(), metadata);
+
+        let sig = signer.sign(b"msg", SigningPurpose::Witness).unwrap();
+        assert_eq!(verify(&sig, b"msg", signer.public_key()), VerifyResult::Valid);
+        assert!(matches!(
+            signer.sign(b"msg", SigningPurpose::General),
+            Err(SignError::UsageDenied { .. })
+        ));
+    }
+
+    #[test]
+    fn expired_key_refuses_to_sign() {
+        let metadata = KeyMetadata {
+            expires_at: Some(1),
+            ..KeyMetadata::default()
+        };
+        let signer = KeystoreSigner::new(KeyPair::generate(), metadata);
+        assert!(matches!(
+            signer.sign(b"msg", SigningPurpose::Witness),
+            Err(SignError::Expired { .. })
+        ));
+    }
+}
@@ -0,0 +1,255 @@
+// crates/schnorr-core/src/transcript.rs
+//
+// Fiat–Shamir transcript over Poseidon.
+//
+// Every challenge in the crate is squeezed from a `Transcript`, so there is
+// one code path to audit for input ordering and domain separation.
+//
+// Two flavours:
+//
+//   Transcript::new(domain)  — domain-separated.  The sponge absorbs
+//                              H(domain) first and H(label) before every
+//                              value and every challenge.
+//   Transcript::circom()     — circuit layout.  Only the values are hashed,
+//                              so the first challenge is exactly
+//                              circomlib's Poseidon(n)(values).  Labels are
+//                              kept as a record of the layout but not
+//                              absorbed, because the circuit cannot see them.
+//
+// Squeezing hashes (previous challenge, pending values...) in chunks of at
+// most 12 elements (light-poseidon's maximum width), chaining the running
+// digest into the next chunk.  In separated mode each squeeze is framed: its
+// first block starts with the number of elements squeezed, and every
+// continuation block starts with a fixed tag, so a chained digest cannot pass
+// for a value or for the start of another squeeze.  Hashers come from a
+// `profile::ChallengeProfile` (the default profile unless
+// `circom_with_profile` is used).
+
+use ark_bn254::Fr as Bn254Fr;
+use crate::curve::{bn254_to_bjj_scalar, BjjPoint, BjjScalar};
+use crate::hash::hash_message_to_field;
+use crate::profile::{default_profile, ChallengeProfile};
+
+/// Maximum number of inputs to a single circom-compatible Poseidon call.
+const MAX_POSEIDON_INPUTS: usize = 12;
+
+/// Leads every continuation block of a framed squeeze.
+const CONTINUATION_TAG: &[u8] = b"bjj-schnorr/transcript/continuation";
+
+/// A Poseidon-based Fiat–Shamir transcript.
+#[derive(Clone, Debug)]
+pub struct Transcript {
+    /// Whether labels and the domain tag are absorbed into the sponge.
+    separated: bool,
+    /// Source of the Poseidon hashers.
+    profile: &'static ChallengeProfile,
+    /// Last squeezed challenge, chained into the next squeeze.
+    state: Option<Bn254Fr>,
+    /// Elements absorbed since the last squeeze.
+    pending: Vec<Bn254Fr>,
+    /// Labels in the order they were appended (including challenges).
+    layout: Vec<&'static str>,
+}
+
+impl Transcript {
+    /// Start a domain-separated transcript.
+    pub fn new(domain: &[u8]) -> Self {
+        Transcript {
+            separated: true,
+            profile: default_profile(),
+            state: None,
+            pending: vec![hash_message_to_field(domain)],
+            layout: Vec::new(),
+        }
+    }
+
+    /// Start a transcript whose challenges match a circuit's raw
+    /// `Poseidon(n)` layout.  Use only where a circuit fixes the inputs.
+    pub fn circom() -> Self {
+        Self::circom_with_profile(default_profile())
+    }
+
+    /// [`Transcript::circom`] hashing with the given profile's Poseidon.
+    pub fn circom_with_profile(profile: &'static ChallengeProfile) -> Self {
+        Transcript {
+            separated: false,
+            profile,
+            state: None,
+            pending: Vec::new(),
+            layout: Vec::new(),
+        }
+    }
+
+    /// Absorb a labeled BN254 field element.
+    pub fn append_field(&mut self, label: &'static str, value: &Bn254Fr) {
+        self.absorb_label(label);
+        self.pending.push(*value);
+    }
+
+    /// Absorb a labeled byte string (mapped to F_p via SHA-256 mod p).
+    pub fn append_bytes(&mut self, label: &'static str, bytes: &[u8]) {
+        self.append_field(label, &hash_message_to_field(bytes));
+    }
+
+    /// Absorb both coordinates of a labeled curve point (x first).
+    pub fn append_point(&mut self, label: &'static str, point: &BjjPoint) {
+        self.absorb_label(label);
+        let (x, y) = point.coords();
+        self.pending.push(x);
+        self.pending.push(y);
+    }
+
+    /// Squeeze a challenge in F_p.
+    pub fn challenge_field(&mut self, label: &'static str) -> Bn254Fr {
+        self.absorb_label(label);
+
+        let mut inputs: Vec<Bn254Fr> = self.state.take().into_iter().collect();
+        inputs.append(&mut self.pending);
+        assert!(!inputs.is_empty(), "cannot squeeze an empty transcript");
+
+        let challenge = if self.separated {
+            poseidon_chain_framed(self.profile, &inputs)
+        } else {
+            poseidon_chain(self.profile, &inputs)
+        };
+        self.state = Some(challenge);
+        challenge
+    }
+
+    /// Squeeze a challenge reduced into the BJJ scalar field Z_n.
+    pub fn challenge_scalar(&mut self, label: &'static str) -> BjjScalar {
+        bn254_to_bjj_scalar(&self.challenge_field(label))
+    }
+
+    /// Labels appended so far, in order.
+    pub fn layout(&self) -> &[&'static str] {
+        &self.layout
+    }
+
+    fn absorb_label(&mut self, label: &'static str) {
+        self.layout.push(label);
+        if self.separated {
+            self.pending.push(hash_message_to_field(label.as_bytes()));
+        }
+    }
+}
+
+/// Hash an arbitrary-length input with chained Poseidon calls.
+///
+/// Inputs that fit in one call are hashed directly, so short transcripts are
+/// exactly `Poseidon(n)(inputs)`.
+fn poseidon_chain(profile: &ChallengeProfile, inputs: &[Bn254Fr]) -> Bn254Fr {
+    let first = inputs.len().min(MAX_POSEIDON_INPUTS);
+    let mut acc = poseidon(profile, &inputs[..first]);
+
+    for chunk in inputs[first..].chunks(MAX_POSEIDON_INPUTS - 1) {
+        let mut block = Vec::with_capacity(chunk.len() + 1);
+        block.push(acc);
+        block.extend_from_slice(chunk);
+        acc = poseidon(profile, &block);
+    }
+
+    acc
+}
+
+/// [`poseidon_chain`] with length and continuation framing:
+///
+///   P(len, x1..x11), then P(tag, acc, next 10 inputs)...
+fn poseidon_chain_framed(profile: &ChallengeProfile, inputs: &[Bn254Fr]) -> Bn254Fr {
+    let first = inputs.len().min(MAX_POSEIDON_INPUTS - 1);
+    let mut block = vec![Bn254Fr::from(inputs.len() as u64)];
+    block.extend_from_slice(&inputs[..first]);
+    let mut acc = poseidon(profile, &block);
+
+    let tag = hash_message_to_field(CONTINUATION_TAG);
+    for chunk in inputs[first..].chunks(MAX_POSEIDON_INPUTS - 2) {
+        let mut block = Vec::with_capacity(chunk.len() + 2);
+        block.push(tag);
+        block.push(acc);
+        block.extend_from_slice(chunk);
+        acc = poseidon(profile, &block);
+    }
+
+    acc
+}
+
+fn poseidon(profile: &ChallengeProfile, inputs: &[Bn254Fr]) -> Bn254Fr {
+    profile
+        .hasher(inputs.len())
+        .hash(inputs)
+        .expect("Poseidon hash failed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn circom_transcript_is_raw_poseidon() {
+        let vals: Vec<Bn254Fr> = (1u64..=4).map(Bn254Fr::from).collect();
+        let mut t = Transcript::circom();
+        for v in &vals {
+            t.append_field("v", v);
+        }
+        assert_eq!(t.challenge_field("e"), poseidon(default_profile(), &vals));
+    }
+
+    #[test]
+    fn domains_separate_challenges() {
+        let one = Bn254Fr::from(1u64);
+        let mut t1 = Transcript::new(b"proto-a");
+        let mut t2 = Transcript::new(b"proto-b");
+        t1.append_field("x", &one);
+        t2.append_field("x", &one);
+        assert_ne!(t1.challenge_field("c"), t2.challenge_field("c"));
+    }
+
+    #[test]
+    fn labels_bind_in_separated_mode() {
+        let one = Bn254Fr::from(1u64);
+        let mut t1 = Transcript::new(b"proto");
+        let mut t2 = Transcript::new(b"proto");
+        t1.append_field("x", &one);
+        t2.append_field("y", &one);
+        assert_ne!(t1.challenge_field("c"), t2.challenge_field("c"));
+    }
+
+    #[test]
+    fn successive_challenges_chain() {
+        let mut t = Transcript::new(b"proto");
+        t.append_bytes("msg", b"hello");
+        let c1 = t.challenge_field("c1");
+        let c2 = t.challenge_field("c2");
+        assert_ne!(c1, c2);
+        assert_eq!(t.layout(), &["msg", "c1", "c2"]);
+    }
+
+    #[test]
+    fn long_inputs_are_chained() {
+        let mut t = Transcript::circom();
+        for i in 0..30u64 {
+            t.append_field("v", &Bn254Fr::from(i));
+        }
+        let mut t2 = t.clone();
+        t2.append_field("v", &Bn254Fr::from(30u64));
+        assert_ne!(t.challenge_field("e"), t2.challenge_field("e"));
+    }
+
+    #[test]
+    fn framing_separates_chained_layouts() {
+        let p = default_profile();
+        let vals: Vec<Bn254Fr> = (1u64..=13).map(Bn254Fr::from).collect();
+        let folded = [poseidon(p, &vals[..12]), vals[12]];
+
+        // Unframed, a continuation block is indistinguishable from a
+        // two-element input that starts with the running digest.
+        assert_eq!(poseidon_chain(p, &vals), poseidon_chain(p, &folded));
+        assert_ne!(poseidon_chain_framed(p, &vals), poseidon_chain_framed(p, &folded));
+
+        // Likewise a chained squeeze (previous challenge, values...) and a
+        // continuation of one longer squeeze.
+        let framed_first = poseidon_chain_framed(p, &vals[..11]);
+        let chained = poseidon_chain_framed(p, &[framed_first, vals[11], vals[12]]);
+        assert_ne!(chained, poseidon_chain_framed(p, &vals));
+    }
+}
@@ -5,14 +5,19 @@
 //   2. e' = Poseidon(R'.x, PK.x, PK.y, H(m))
 //   3. Accept iff e' == e
 //
+// The challenge hash is the one named by the signature's profile.
+//
 // Note: `e` is the full Poseidon output in F_p (not reduced mod n).
 // The scalar multiplication `e · PK` naturally reduces mod n because
 // the group has order n.
 
 use ark_bn254::Fr as Bn254Fr;
 
+use crate::binding::ChainBinding;
 use crate::curve::BjjPoint;
-use crate::hash::{hash_message_to_field, schnorr_challenge};
+use crate::hash::{
+    bind_context, context_tag, hash_message_to_field, schnorr_challenge_with_profile,
+};
 use crate::keypair::PublicKey;
 use crate::sign::Signature;
 
@@ -25,6 +30,41 @@ pub enum VerifyResult {
 
 /// Verify a Schnorr signature against a public key and message.
 pub fn verify(sig: &Signature, message: &[u8], pk: &PublicKey) -> VerifyResult {
+    verify_hashed(sig, &hash_message_to_field(message), pk)
+}
+
+/// Verify a signature produced by [`Signature::sign_with_context`].
+pub fn verify_with_context(
+    sig: &Signature,
+    context: &[u8],
+    message: &[u8],
+    pk: &PublicKey,
+) -> VerifyResult {
+    verify_with_context_tag(sig, &context_tag(context), message, pk)
+}
+
+/// Verify a signature produced by [`Signature::sign_with_chain_binding`].
+pub fn verify_with_chain_binding(
+    sig: &Signature,
+    binding: &ChainBinding,
+    message: &[u8],
+    pk: &PublicKey,
+) -> VerifyResult {
+    verify_with_context_tag(sig, &binding.context_tag(), message, pk)
+}
+
+/// Verify a signature produced by [`Signature::sign_with_context_tag`].
+pub fn verify_with_context_tag(
+    sig: &Signature,
+    ctx_tag: &Bn254Fr,
+    message: &[u8],
+    pk: &PublicKey,
+) -> VerifyResult {
+    let msg_hash = bind_context(ctx_tag, &hash_message_to_field(message));
+    verify_hashed(sig, &msg_hash, pk)
+}
+
+fn verify_hashed(sig: &Signature, msg_hash: &Bn254Fr, pk: &PublicKey) -> VerifyResult {
     let g = BjjPoint::generator();
 
     // R' = s · G  +  e · PK
@@ -35,9 +75,9 @@ pub fn verify(sig: &Signature, message: &[u8], pk: &PublicKey) -> VerifyResult {
     // Recompute challenge from R'
     let (r_prime_x, _) = r_prime.coords();
     let (pk_x, pk_y) = pk.coords();
-    let msg_hash = hash_message_to_field(message);
 
-    let e_check: Bn254Fr = schnorr_challenge(&r_prime_x, &pk_x, &pk_y, &msg_hash);
+    let e_check: Bn254Fr =
+        schnorr_challenge_with_profile(sig.profile, &r_prime_x, &pk_x, &pk_y, msg_hash);
 
     if e_check == sig.e {
         VerifyResult::Valid
@@ -92,4 +132,31 @@ mod tests {
         let sig = Signature::sign(&kp, &msg);
         assert_eq!(verify(&sig, &msg, &kp.pk), VerifyResult::Valid);
     }
+
+    #[test]
+    fn context_signature_verifies_only_in_its_context() {
+        let kp = KeyPair::generate();
+        let sig = Signature::sign_with_context(&kp, b"app-a", b"msg");
+        assert_eq!(verify_with_context(&sig, b"app-a", b"msg", &kp.pk), VerifyResult::Valid);
+        assert_eq!(verify_with_context(&sig, b"app-b", b"msg", &kp.pk), VerifyResult::Invalid);
+        assert_eq!(verify(&sig, b"msg", &kp.pk), VerifyResult::Invalid);
+    }
+
+    #[test]
+    fn chain_bound_signature_rejects_other_deployments() {
+        let kp = KeyPair::generate();
+        let contract = [0x11u8; 20];
+        let mainnet = ChainBinding::new(1, contract);
+        let testnet = ChainBinding::new(5, contract);
+        let other_contract = ChainBinding::new(1, [0x22u8; 20]);
+
+        let sig = Signature::sign_with_chain_binding(&kp, &mainnet, b"msg");
+        assert_eq!(verify_with_chain_binding(&sig, &mainnet, b"msg", &kp.pk), VerifyResult::Valid);
+        assert_eq!(verify_with_chain_binding(&sig, &testnet, b"msg", &kp.pk), VerifyResult::Invalid);
+        assert_eq!(
+            verify_with_chain_binding(&sig, &other_contract, b"msg", &kp.pk),
+            VerifyResult::Invalid
+        );
+        assert_eq!(verify(&sig, b"msg", &kp.pk), VerifyResult::Invalid);
+    }
 }
@@ -0,0 +1,59 @@
+// crates/schnorr-witness/build.rs
+//
+// Fingerprint the code that computes witnesses: SHA-256 over the manifests
+// and sources of schnorr-core and schnorr-witness (relative path and
+// contents of every file, in sorted order).  Exposed to the crate as
+// SCHNORR_SOURCE_SHA256 and recorded in proving manifests, since
+// CARGO_PKG_VERSION does not change between commits.
+
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+fn main() {
+    let witness = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let root = witness.parent().expect("crate lives in crates/").to_path_buf();
+    let core = root.join("schnorr-core");
+
+    let mut files = Vec::new();
+    for crate_dir in [&core, &witness] {
+        for entry in ["Cargo.toml", "build.rs", "src"] {
+            let path = crate_dir.join(entry);
+            if path.exists() {
+                println!("cargo:rerun-if-changed={}", path.display());
+                collect(&path, &mut files);
+            }
+        }
+    }
+    files.sort_by_key(|path| relative(&root, path));
+
+    let mut hasher = Sha256::new();
+    for path in &files {
+        let contents = std::fs::read(path).expect("failed to read source file");
+        hasher.update(relative(&root, path).as_bytes());
+        hasher.update([0]);
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(&contents);
+    }
+    let digest: String = hasher.finalize().iter().map(|b| format!("{b:02x}")).collect();
+    println!("cargo:rustc-env=SCHNORR_SOURCE_SHA256={digest}");
+}
+
+fn collect(path: &Path, files: &mut Vec<PathBuf>) {
+    if path.is_dir() {
+        for entry in std::fs::read_dir(path).expect("failed to list source directory") {
+            collect(&entry.expect("failed to list source directory").path(), files);
+        }
+    } else {
+        files.push(path.to_path_buf());
+    }
+}
+
+/// `path` relative to `crates/`, with `/` separators on every platform.
+fn relative(root: &Path, path: &Path) -> String {
+    let path = path.strip_prefix(root).unwrap_or(path);
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
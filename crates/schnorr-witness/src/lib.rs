@@ -1,3 +1,11 @@
+pub mod manifest;
+#[cfg(feature = "wasm")]
+pub mod wasm_witness;
 pub mod witness_builder;
+pub mod witness_diff;
 
-pub use witness_builder::{build_witness_input, export_witness_json};
+pub use witness_builder::{
+    build_witness_input, build_witness_input_for_public_key, build_witness_input_with_chain_binding,
+    build_witness_input_with_context, export_witness_json, from_signed_message, write_witness_json,
+    WitnessError, WitnessOptions,
+};
@@ -1,33 +1,192 @@
-use schnorr_core::{verify, KeyPair, Signature, VerifyResult};
-use schnorr_witness::witness_builder;
-use std::path::PathBuf;
+use schnorr_core::{
+    ChainBinding, KeyMetadata, KeyPair, KeyUsage, Keystore, SchnorrSigner, SignedMessage,
+    SigningDomain, SigningPurpose, VerifyResult,
+};
+use schnorr_witness::manifest::Manifest;
+use schnorr_witness::witness_builder::{self, WitnessOptions};
+use schnorr_witness::witness_diff;
+use std::path::{Path, PathBuf};
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
 
+    // `sign` is the default so existing `schnorr-witness --message ...`
+    // invocations keep working.
+    match args.get(1).map(String::as_str) {
+        Some("sign") => run_sign(&args[2..]),
+        Some("keygen") => run_keygen(&args[2..]),
+        Some("diff") => run_diff(&args[2..]),
+        Some("verify-manifest") => run_verify_manifest(&args[2..]),
+        _ => run_sign(&args[1..]),
+    }
+}
+
+fn print_usage() {
+    eprintln!("Usage: schnorr-witness [sign] [OPTIONS]");
+    eprintln!("       schnorr-witness keygen --output PATH [OPTIONS]");
+    eprintln!("       schnorr-witness diff LEFT.json (RIGHT.json | --keystore PATH [OPTIONS])");
+    eprintln!("       schnorr-witness verify-manifest MANIFEST.json");
+    eprintln!();
+    eprintln!("sign:");
+    eprintln!("  --message, -m  Message to sign (default: 'hello world')");
+    eprintln!("  --output, -o   Output JSON path (default: build/input.json)");
+    eprintln!("  --context, -c  Signing context; emits ctxTag for schnorr_ctx.circom");
+    eprintln!("  --chain-id N --verifying-contract 0xADDR");
+    eprintln!("                 Bind to a deployment; emits inputs for schnorr_chain.circom");
+    eprintln!("  --keystore, -k PATH   Sign with a stored key instead of a fresh one");
+    eprintln!("  --export-pubkey PATH  Also write the public key in circomlibjs form");
+    eprintln!("  --manifest PATH       Also write a proving manifest (artifact hashes)");
+    eprintln!("  --circuit PATH        Circuit source to hash, repeatable");
+    eprintln!("                        (default: circuits/<name>.circom + schnorr_verify.circom)");
+    eprintln!("  --zkey PATH           Proving key to hash (default: build/<name>.zkey)");
+    eprintln!("  --wtns PATH           Also compute the full .wtns in-process (feature 'wasm')");
+    eprintln!("  --wasm PATH           Circuit wasm (default: build/<name>_js/<name>.wasm)");
+    eprintln!("  --no-json             Skip writing the witness input JSON");
+    eprintln!("                        (--manifest then hashes it in memory)");
+    eprintln!();
+    eprintln!("keygen:");
+    eprintln!("  --output, -o   Keystore path to write");
+    eprintln!("  --usage        'general' (default) or 'witness-only'");
+    eprintln!("  --expires-in   Key lifetime in seconds from now");
+    eprintln!();
+    eprintln!("diff:");
+    eprintln!("  Compare two witness inputs, or one against the witness recomputed");
+    eprintln!("  from --keystore with --message / --context / --chain-id (same as for sign).");
+}
+
+/// Combine the domain flags, exiting if they conflict or are incomplete.
+fn domain_from_flags(
+    context: Option<String>,
+    chain_id: Option<String>,
+    verifying_contract: Option<String>,
+) -> SigningDomain {
+    match (context, chain_id, verifying_contract) {
+        (None, None, None) => SigningDomain::None,
+        (Some(ctx), None, None) => SigningDomain::Context(ctx.into_bytes()),
+        (None, Some(chain_id), Some(contract)) => {
+            let Ok(chain_id) = chain_id.parse() else {
+                eprintln!("--chain-id must be an unsigned 64-bit integer");
+                std::process::exit(1);
+            };
+            let Some(binding) = ChainBinding::from_hex_address(chain_id, &contract) else {
+                eprintln!("--verifying-contract must be a 20-byte hex address");
+                std::process::exit(1);
+            };
+            SigningDomain::Chain(binding)
+        }
+        (Some(_), _, _) => {
+            eprintln!("--context cannot be combined with --chain-id / --verifying-contract");
+            std::process::exit(1);
+        }
+        _ => {
+            eprintln!("--chain-id and --verifying-contract must be given together");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// The circuit that checks signatures under `domain`.
+fn circuit_name(domain: &SigningDomain) -> &'static str {
+    match domain {
+        SigningDomain::None => "schnorr",
+        SigningDomain::Context(_) => "schnorr_ctx",
+        SigningDomain::Chain(_) => "schnorr_chain",
+    }
+}
+
+/// Witness input for an envelope this process just signed, or exit.
+fn envelope_witness(signed: &SignedMessage) -> serde_json::Value {
+    witness_builder::from_signed_message(signed, WitnessOptions::default()).unwrap_or_else(|e| {
+        eprintln!("✗ {e}");
+        std::process::exit(1);
+    })
+}
+
+/// Fetch the value following a flag, or exit with an error.
+fn flag_value(args: &[String], i: usize) -> String {
+    match args.get(i) {
+        Some(v) => v.clone(),
+        None => {
+            eprintln!("Missing value for {}", args[i - 1]);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_sign(args: &[String]) {
     let mut message = String::from("hello world");
     let mut output = PathBuf::from("build/input.json");
+    let mut context: Option<String> = None;
+    let mut chain_id: Option<String> = None;
+    let mut verifying_contract: Option<String> = None;
+    let mut keystore: Option<PathBuf> = None;
+    let mut export_pubkey: Option<PathBuf> = None;
+    let mut manifest: Option<PathBuf> = None;
+    let mut circuits: Vec<PathBuf> = Vec::new();
+    let mut zkey: Option<PathBuf> = None;
+    let mut wtns: Option<PathBuf> = None;
+    let mut wasm: Option<PathBuf> = None;
+    let mut write_json = true;
 
     // Simple argument parsing
-    let mut i = 1;
+    let mut i = 0;
     while i < args.len() {
         match args[i].as_str() {
             "--message" | "-m" => {
                 i += 1;
-                if i < args.len() {
-                    message = args[i].clone();
-                }
+                message = flag_value(args, i);
             }
             "--output" | "-o" => {
                 i += 1;
-                if i < args.len() {
-                    output = PathBuf::from(&args[i]);
+                output = PathBuf::from(flag_value(args, i));
+            }
+            "--context" | "-c" => {
+                i += 1;
+                context = Some(flag_value(args, i));
+            }
+            "--chain-id" => {
+                i += 1;
+                chain_id = Some(flag_value(args, i));
+            }
+            "--verifying-contract" => {
+                i += 1;
+                verifying_contract = Some(flag_value(args, i));
+            }
+            "--keystore" | "-k" => {
+                i += 1;
+                keystore = Some(PathBuf::from(flag_value(args, i)));
+            }
+            "--export-pubkey" => {
+                i += 1;
+                export_pubkey = Some(PathBuf::from(flag_value(args, i)));
+            }
+            "--manifest" => {
+                i += 1;
+                manifest = Some(PathBuf::from(flag_value(args, i)));
+            }
+            "--circuit" => {
+                i += 1;
+                circuits.push(PathBuf::from(flag_value(args, i)));
+            }
+            "--zkey" => {
+                i += 1;
+                zkey = Some(PathBuf::from(flag_value(args, i)));
+            }
+            "--wtns" => {
+                if !cfg!(feature = "wasm") {
+                    eprintln!("--wtns requires building schnorr-witness with --features wasm");
+                    std::process::exit(1);
                 }
+                i += 1;
+                wtns = Some(PathBuf::from(flag_value(args, i)));
             }
+            "--wasm" => {
+                i += 1;
+                wasm = Some(PathBuf::from(flag_value(args, i)));
+            }
+            "--no-json" => write_json = false,
             "--help" | "-h" => {
-                eprintln!("Usage: schnorr-witness [OPTIONS]");
-                eprintln!("  --message, -m  Message to sign (default: 'hello world')");
-                eprintln!("  --output, -o   Output JSON path (default: build/input.json)");
+                print_usage();
                 std::process::exit(0);
             }
             _ => {
@@ -37,31 +196,334 @@ fn main() {
         }
         i += 1;
     }
+    let domain = domain_from_flags(context, chain_id, verifying_contract);
+    let circuit_name = circuit_name(&domain);
 
-    eprintln!("[1/4] Generating keypair...");
-    let keypair = KeyPair::generate();
-    let (pk_x, pk_y) = keypair.pk.coords();
+    // A stored key signs through its metadata policy; a fresh key has none.
+    let signer: Box<dyn SchnorrSigner> = match &keystore {
+        Some(path) => {
+            eprintln!("[1/4] Loading keystore {:?}...", path);
+            let signer = Keystore::load(path)
+                .and_then(|ks| ks.signer())
+                .unwrap_or_else(|e| {
+                    eprintln!("  ✗ {e}");
+                    std::process::exit(1);
+                });
+            Box::new(signer)
+        }
+        None => {
+            eprintln!("[1/4] Generating keypair...");
+            Box::new(KeyPair::generate())
+        }
+    };
+    let (pk_x, pk_y) = signer.public_key().coords();
     eprintln!("  PK.x = {}", schnorr_core::curve::bn254_to_dec_string(&pk_x));
     eprintln!("  PK.y = {}", schnorr_core::curve::bn254_to_dec_string(&pk_y));
 
     eprintln!("[2/4] Signing message: {:?}", &message);
-    let sig = Signature::sign(&keypair, message.as_bytes());
+    match &domain {
+        SigningDomain::None => {}
+        SigningDomain::Context(ctx) => {
+            eprintln!("  context = {:?}", String::from_utf8_lossy(ctx))
+        }
+        SigningDomain::Chain(binding) => eprintln!(
+            "  chain id = {}, verifying contract = {}",
+            binding.chain_id,
+            binding.contract_hex()
+        ),
+    }
+    let purpose = SigningPurpose::Witness;
+    let signed = SignedMessage::sign_with(signer.as_ref(), message.as_bytes(), domain, purpose)
+        .unwrap_or_else(|e| {
+            eprintln!("  ✗ Signing refused: {e}");
+            std::process::exit(1);
+        });
+    let sig = &signed.signature;
     eprintln!("  e = {}", schnorr_core::curve::bn254_to_dec_string(&sig.e));
     eprintln!("  s = {}", sig.s.to_dec_string());
 
     eprintln!("[3/4] Verifying signature (Rust)...");
-    let result = verify(&sig, message.as_bytes(), &keypair.pk);
+    let result = signed.verify();
     assert_eq!(result, VerifyResult::Valid, "Rust verification failed!");
     eprintln!("  ✓ Signature valid");
 
-    eprintln!("[4/4] Exporting witness JSON to {:?}...", &output);
-    if let Some(parent) = output.parent() {
-        std::fs::create_dir_all(parent).expect("failed to create output directory");
+    let witness = envelope_witness(&signed);
+    if write_json {
+        eprintln!("[4/4] Exporting witness JSON to {:?}...", &output);
+        create_parent_dir(&output);
+        witness_builder::write_witness_json(&witness, &output)
+            .expect("failed to write witness JSON");
+    }
+
+    let wasm = wasm
+        .unwrap_or_else(|| PathBuf::from(format!("build/{circuit_name}_js/{circuit_name}.wasm")));
+    if let Some(path) = &wtns {
+        eprintln!("  computing .wtns in-process with {:?}...", &wasm);
+        create_parent_dir(path);
+        write_wtns(&witness, &wasm, path);
+        eprintln!("  ✓ witness written to {:?}", path);
+    }
+
+    if let Some(path) = &export_pubkey {
+        let exported = serde_json::to_string_pretty(&signer.public_key().to_circomlibjs()).unwrap();
+        std::fs::write(path, exported).expect("failed to write public key JSON");
+        eprintln!("  public key (circomlibjs) written to {:?}", path);
+    }
+
+    if let Some(path) = &manifest {
+        if circuits.is_empty() {
+            circuits.push(PathBuf::from(format!("circuits/{circuit_name}.circom")));
+            circuits.push(PathBuf::from("circuits/schnorr_verify.circom"));
+        }
+
+        let mut m = if write_json {
+            Manifest::for_witness(&witness, &output).expect("failed to hash witness input")
+        } else {
+            Manifest::for_unwritten_witness(&witness)
+        };
+        for circuit in &circuits {
+            m = m.with_artifact("circuit", circuit).unwrap_or_else(|e| {
+                eprintln!("  ✗ cannot hash circuit {:?}: {e}", circuit);
+                std::process::exit(1);
+            });
+        }
+        // An explicit --zkey must exist.  The default one may not have been
+        // set up yet; it is then recorded as absent and fails verification.
+        match &zkey {
+            Some(zkey) => {
+                m = m.with_artifact("zkey", zkey).unwrap_or_else(|e| {
+                    eprintln!("  ✗ cannot hash zkey {:?}: {e}", zkey);
+                    std::process::exit(1);
+                });
+            }
+            None => {
+                let default = PathBuf::from(format!("build/{circuit_name}.zkey"));
+                if default.exists() {
+                    m = m.with_artifact("zkey", &default).expect("failed to hash zkey");
+                } else {
+                    eprintln!("  ! zkey {:?} not found; recorded as absent", default);
+                    m = m.with_absent_artifact("zkey", &default);
+                }
+            }
+        }
+        if let Some(wtns) = &wtns {
+            m = m.with_artifact("wasm", &wasm).expect("failed to hash wasm");
+            m = m.with_artifact("wtns", wtns).expect("failed to hash .wtns");
+        }
+        m.save(path).expect("failed to write manifest");
+        eprintln!("  manifest written to {:?}", path);
     }
-    witness_builder::export_witness_json(&sig, &keypair, message.as_bytes(), &output)
-        .expect("failed to write witness JSON");
 
     // Also print the JSON to stdout for inspection
-    let witness = witness_builder::build_witness_input(&sig, &keypair, message.as_bytes());
     println!("{}", serde_json::to_string_pretty(&witness).unwrap());
 }
+
+fn run_verify_manifest(args: &[String]) {
+    let path = match args {
+        [path] if !path.starts_with('-') => PathBuf::from(path),
+        _ => {
+            print_usage();
+            std::process::exit(1);
+        }
+    };
+
+    let manifest = Manifest::load(&path).unwrap_or_else(|e| {
+        eprintln!("✗ cannot load manifest {:?}: {e}", path);
+        std::process::exit(1);
+    });
+
+    let mismatches = manifest.verify();
+    if mismatches.is_empty() {
+        eprintln!("✓ manifest {:?} matches ({} artifacts)", path, manifest.artifacts.len());
+    } else {
+        for m in &mismatches {
+            eprintln!("✗ {m}");
+        }
+        std::process::exit(1);
+    }
+}
+
+fn create_parent_dir(path: &Path) {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).expect("failed to create output directory");
+    }
+}
+
+#[cfg(feature = "wasm")]
+fn write_wtns(witness: &serde_json::Value, wasm: &Path, output: &Path) {
+    use schnorr_witness::wasm_witness::WitnessCalculator;
+
+    let bin = WitnessCalculator::from_file(wasm)
+        .and_then(|mut calc| calc.calculate_wtns_bin(witness))
+        .unwrap_or_else(|e| {
+            eprintln!("  ✗ {e}");
+            std::process::exit(1);
+        });
+    std::fs::write(output, bin).expect("failed to write .wtns");
+}
+
+#[cfg(not(feature = "wasm"))]
+fn write_wtns(_witness: &serde_json::Value, _wasm: &Path, _output: &Path) {
+    unreachable!("--wtns is rejected at argument parsing without the wasm feature");
+}
+
+fn run_keygen(args: &[String]) {
+    let mut output: Option<PathBuf> = None;
+    let mut usage = KeyUsage::General;
+    let mut expires_in: Option<u64> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--output" | "-o" => {
+                i += 1;
+                output = Some(PathBuf::from(flag_value(args, i)));
+            }
+            "--usage" => {
+                i += 1;
+                usage = match flag_value(args, i).as_str() {
+                    "general" => KeyUsage::General,
+                    "witness-only" => KeyUsage::WitnessOnly,
+                    other => {
+                        eprintln!("Unknown usage policy: {other}");
+                        std::process::exit(1);
+                    }
+                };
+            }
+            "--expires-in" => {
+                i += 1;
+                let secs = flag_value(args, i).parse().unwrap_or_else(|_| {
+                    eprintln!("--expires-in must be a number of seconds");
+                    std::process::exit(1);
+                });
+                expires_in = Some(secs);
+            }
+            "--help" | "-h" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            _ => {
+                eprintln!("Unknown argument: {}", args[i]);
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let Some(output) = output else {
+        eprintln!("keygen requires --output PATH");
+        std::process::exit(1);
+    };
+
+    let now = schnorr_core::signer::unix_now();
+    let expires_at = expires_in.map(|secs| {
+        now.checked_add(secs).unwrap_or_else(|| {
+            eprintln!("--expires-in is too large");
+            std::process::exit(1);
+        })
+    });
+    let metadata = KeyMetadata {
+        created_at: Some(now),
+        expires_at,
+        usage,
+    };
+    let keypair = KeyPair::generate();
+    Keystore::new(&keypair, Some(metadata))
+        .save(&output)
+        .expect("failed to write keystore");
+
+    let (pk_x, pk_y) = keypair.pk.coords();
+    eprintln!("Keystore written to {:?}", &output);
+    eprintln!("  PK.x = {}", schnorr_core::curve::bn254_to_dec_string(&pk_x));
+    eprintln!("  PK.y = {}", schnorr_core::curve::bn254_to_dec_string(&pk_y));
+}
+
+fn run_diff(args: &[String]) {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    let mut message = String::from("hello world");
+    let mut context: Option<String> = None;
+    let mut chain_id: Option<String> = None;
+    let mut verifying_contract: Option<String> = None;
+    let mut keystore: Option<PathBuf> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--message" | "-m" => {
+                i += 1;
+                message = flag_value(args, i);
+            }
+            "--context" | "-c" => {
+                i += 1;
+                context = Some(flag_value(args, i));
+            }
+            "--chain-id" => {
+                i += 1;
+                chain_id = Some(flag_value(args, i));
+            }
+            "--verifying-contract" => {
+                i += 1;
+                verifying_contract = Some(flag_value(args, i));
+            }
+            "--keystore" | "-k" => {
+                i += 1;
+                keystore = Some(PathBuf::from(flag_value(args, i)));
+            }
+            "--help" | "-h" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            arg if !arg.starts_with('-') => paths.push(PathBuf::from(arg)),
+            _ => {
+                eprintln!("Unknown argument: {}", args[i]);
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let domain = domain_from_flags(context, chain_id, verifying_contract);
+    let (left, right) = match (paths.as_slice(), &keystore) {
+        ([left, right], None) => (read_witness(left), read_witness(right)),
+        ([left], Some(ks)) => {
+            let signer = Keystore::load(ks)
+                .and_then(|ks| ks.signer())
+                .unwrap_or_else(|e| {
+                    eprintln!("✗ {e}");
+                    std::process::exit(1);
+                });
+            let purpose = SigningPurpose::Witness;
+            let signed = SignedMessage::sign_with(&signer, message.as_bytes(), domain, purpose)
+                .unwrap_or_else(|e| {
+                    eprintln!("✗ Signing refused: {e}");
+                    std::process::exit(1);
+                });
+            let expected = envelope_witness(&signed);
+            (read_witness(left), expected)
+        }
+        _ => {
+            eprintln!("diff takes two witness files, or one witness file and --keystore");
+            std::process::exit(1);
+        }
+    };
+
+    let report = witness_diff::diff(&left, &right);
+    print!("{report}");
+
+    let clean = report.signals.is_empty()
+        && matches!((&report.left, &report.right), (Ok(l), Ok(r)) if l.consistent && r.consistent);
+    if !clean {
+        std::process::exit(1);
+    }
+}
+
+fn read_witness(path: &Path) -> serde_json::Value {
+    let raw = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("✗ cannot read {:?}: {e}", path);
+        std::process::exit(1);
+    });
+    serde_json::from_str(&raw).unwrap_or_else(|e| {
+        eprintln!("✗ {:?} is not valid JSON: {e}", path);
+        std::process::exit(1);
+    })
+}
@@ -0,0 +1,461 @@
+// crates/schnorr-witness/src/manifest.rs
+//
+// Proving manifest: ties a witness input back to the exact circuit, keys
+// and code that produced it.
+//
+//   {
+//     "version": 1,
+//     "code": {
+//       "schnorr-core": "0.1.0", "schnorr-witness": "0.1.0",
+//       "sourceSha256": "…"
+//     },
+//     "challenge": {
+//       "hash": "circomlib-poseidon-bn254-w4",
+//       "layout": ["R.x", "PK.x", "PK.y", "msgHash"],
+//       "msgHashBinding": "none"
+//     },
+//     "inputs": ["e", "msgHash", "pkX", "pkY", "s"],
+//     "artifacts": [
+//       { "role": "witnessInput", "path": "build/input.json", "sha256": "…" },
+//       { "role": "circuit",      "path": "circuits/schnorr.circom", "sha256": "…" },
+//       { "role": "zkey",         "path": "build/schnorr.zkey", "sha256": "…" },
+//       { "role": "wasm",         "path": "build/schnorr_js/schnorr.wasm", "sha256": "…" },
+//       { "role": "wtns",         "path": "build/witness.wtns", "sha256": "…" }
+//     ]
+//   }
+//
+// `challenge.hash` is a `schnorr_core::profile` id.  Circuits reject unknown
+// input signals, so the manifest is where a witness input records its
+// challenge profile.
+//
+// Artifact paths are recorded as given and re-resolved relative to the
+// current directory by `verify`.  An expected artifact that did not exist
+// when the manifest was written is recorded with `"sha256": null`, and
+// `verify` reports it, so a manifest never passes without binding it.
+//
+// With `--no-json` the witness input is never written; it is hashed in
+// memory (as the bytes `write_witness_json` would write) and recorded
+// without a path.  `verify` cannot re-hash it, and relies on the `.wtns`
+// artifact computed from it instead.
+
+use std::fmt;
+use std::path::Path;
+
+use ark_bn254::Fr as Bn254Fr;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use schnorr_core::hash::schnorr_transcript;
+use schnorr_core::profile::default_profile;
+
+/// Current manifest format version.
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Versions of the crates that produced a manifest.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeVersions {
+    #[serde(rename = "schnorr-core")]
+    pub schnorr_core: String,
+    #[serde(rename = "schnorr-witness")]
+    pub schnorr_witness: String,
+    /// SHA-256 of both crates' sources (see `build.rs`).  The package
+    /// versions alone do not change between commits.  Empty in manifests
+    /// written before it was recorded, which therefore never verify.
+    #[serde(rename = "sourceSha256", default)]
+    pub source_sha256: String,
+}
+
+impl CodeVersions {
+    pub fn current() -> Self {
+        CodeVersions {
+            schnorr_core: schnorr_core::VERSION.to_string(),
+            schnorr_witness: env!("CARGO_PKG_VERSION").to_string(),
+            source_sha256: env!("SCHNORR_SOURCE_SHA256").to_string(),
+        }
+    }
+}
+
+/// How the Schnorr challenge was computed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChallengeLayout {
+    /// Challenge profile id.
+    pub hash: String,
+    /// Transcript inputs, in absorption order.
+    pub layout: Vec<String>,
+    /// How the circuit derives the challenge's msgHash from its inputs.
+    pub msg_hash_binding: String,
+}
+
+impl ChallengeLayout {
+    /// The layout this build of the crate uses for the given witness.
+    pub fn current(witness: &Value) -> Self {
+        let zero = Bn254Fr::from(0u64);
+        let transcript = schnorr_transcript(&zero, &zero, &zero, &zero);
+        let msg_hash_binding = if witness.get("chainId").is_some() {
+            "poseidon(poseidon(chainId, verifyingContract), msgHash)"
+        } else if witness.get("ctxTag").is_some() {
+            "poseidon(ctxTag, msgHash)"
+        } else {
+            "none"
+        };
+        ChallengeLayout {
+            hash: default_profile().id.to_string(),
+            layout: transcript.layout().iter().map(|l| l.to_string()).collect(),
+            msg_hash_binding: msg_hash_binding.to_string(),
+        }
+    }
+}
+
+/// SHA-256 of one file used in the proving pipeline.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactHash {
+    pub role: String,
+    /// `None` for a witness input that was hashed in memory, not written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// `None` if the file was absent when the manifest was written.
+    pub sha256: Option<String>,
+}
+
+impl ArtifactHash {
+    pub fn of_file(role: &str, path: &Path) -> std::io::Result<Self> {
+        Ok(ArtifactHash {
+            role: role.to_string(),
+            path: Some(path.to_string_lossy().into_owned()),
+            sha256: Some(sha256_file(path)?),
+        })
+    }
+
+    /// Hash of contents that were never written to a file.
+    pub fn of_bytes(role: &str, bytes: &[u8]) -> Self {
+        ArtifactHash {
+            role: role.to_string(),
+            path: None,
+            sha256: Some(sha256_hex(bytes)),
+        }
+    }
+
+    /// An expected artifact that does not exist.
+    pub fn absent(role: &str, path: &Path) -> Self {
+        ArtifactHash {
+            role: role.to_string(),
+            path: Some(path.to_string_lossy().into_owned()),
+            sha256: None,
+        }
+    }
+}
+
+/// Why a manifest does not match the current inputs.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ManifestMismatch {
+    /// An artifact was absent when the manifest was written.
+    Absent { role: String, path: String },
+    /// An artifact could not be read.
+    Unreadable { role: String, path: String, error: String },
+    /// An artifact's contents changed.
+    Hash { role: String, path: String, expected: String, actual: String },
+    /// The manifest was produced by different code.
+    Code { expected: CodeVersions, actual: CodeVersions },
+    /// The recorded challenge layout differs from this build's.
+    Challenge { expected: ChallengeLayout, actual: ChallengeLayout },
+}
+
+impl fmt::Display for ManifestMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifestMismatch::Absent { role, path } => {
+                write!(f, "{role} {path}: absent when the manifest was written")
+            }
+            ManifestMismatch::Unreadable { role, path, error } => {
+                write!(f, "{role} {path}: cannot read ({error})")
+            }
+            ManifestMismatch::Hash { role, path, expected, actual } => {
+                write!(f, "{role} {path}: sha256 {actual}, manifest has {expected}")
+            }
+            ManifestMismatch::Code { expected, actual } => write!(
+                f,
+                "code versions core {}/witness {} (source {}), \
+                 manifest has core {}/witness {} (source {})",
+                actual.schnorr_core,
+                actual.schnorr_witness,
+                actual.source_sha256,
+                expected.schnorr_core,
+                expected.schnorr_witness,
+                expected.source_sha256
+            ),
+            ManifestMismatch::Challenge { expected, actual } => write!(
+                f,
+                "challenge {} {:?} ({}), manifest has {} {:?} ({})",
+                actual.hash,
+                actual.layout,
+                actual.msg_hash_binding,
+                expected.hash,
+                expected.layout,
+                expected.msg_hash_binding
+            ),
+        }
+    }
+}
+
+/// Reproducibility record for one witness input.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    pub version: u32,
+    pub code: CodeVersions,
+    pub challenge: ChallengeLayout,
+    /// Circuit input signal names present in the witness input.
+    pub inputs: Vec<String>,
+    pub artifacts: Vec<ArtifactHash>,
+}
+
+impl Manifest {
+    /// Start a manifest for a witness input already written to `witness_path`.
+    pub fn for_witness(witness: &Value, witness_path: &Path) -> std::io::Result<Self> {
+        let artifact = ArtifactHash::of_file("witnessInput", witness_path)?;
+        Ok(Self::with_witness_artifact(witness, artifact))
+    }
+
+    /// Start a manifest for a witness input that is not written to disk.
+    pub fn for_unwritten_witness(witness: &Value) -> Self {
+        let json = crate::witness_builder::witness_json(witness);
+        let artifact = ArtifactHash::of_bytes("witnessInput", json.as_bytes());
+        Self::with_witness_artifact(witness, artifact)
+    }
+
+    fn with_witness_artifact(witness: &Value, artifact: ArtifactHash) -> Self {
+        let mut inputs: Vec<String> = witness
+            .as_object()
+            .map(|obj| obj.keys().cloned().collect())
+            .unwrap_or_default();
+        inputs.sort();
+
+        Manifest {
+            version: MANIFEST_VERSION,
+            code: CodeVersions::current(),
+            challenge: ChallengeLayout::current(witness),
+            inputs,
+            artifacts: vec![artifact],
+        }
+    }
+
+    /// Record another pipeline file (circuit source, zkey, wasm, ...).
+    pub fn with_artifact(mut self, role: &str, path: &Path) -> std::io::Result<Self> {
+        self.artifacts.push(ArtifactHash::of_file(role, path)?);
+        Ok(self)
+    }
+
+    /// Record a pipeline file that should exist but does not.
+    pub fn with_absent_artifact(mut self, role: &str, path: &Path) -> Self {
+        self.artifacts.push(ArtifactHash::absent(role, path));
+        self
+    }
+
+    /// Re-hash every artifact and compare code and challenge parameters.
+    ///
+    /// An empty result means the manifest still describes the inputs.
+    pub fn verify(&self) -> Vec<ManifestMismatch> {
+        let mut mismatches = Vec::new();
+
+        let code = CodeVersions::current();
+        if code != self.code {
+            mismatches.push(ManifestMismatch::Code {
+                expected: self.code.clone(),
+                actual: code,
+            });
+        }
+
+        let mut witness: Option<Value> = None;
+        for artifact in &self.artifacts {
+            // Hashed in memory: there is no file to re-hash.
+            let Some(recorded) = &artifact.path else {
+                continue;
+            };
+            let path = Path::new(recorded);
+            let Some(expected) = &artifact.sha256 else {
+                mismatches.push(ManifestMismatch::Absent {
+                    role: artifact.role.clone(),
+                    path: recorded.clone(),
+                });
+                continue;
+            };
+            match sha256_file(path) {
+                Ok(actual) if &actual == expected => {}
+                Ok(actual) => mismatches.push(ManifestMismatch::Hash {
+                    role: artifact.role.clone(),
+                    path: recorded.clone(),
+                    expected: expected.clone(),
+                    actual,
+                }),
+                Err(e) => mismatches.push(ManifestMismatch::Unreadable {
+                    role: artifact.role.clone(),
+                    path: recorded.clone(),
+                    error: e.to_string(),
+                }),
+            }
+            if artifact.role == "witnessInput" {
+                witness = std::fs::read_to_string(path)
+                    .ok()
+                    .and_then(|raw| serde_json::from_str(&raw).ok());
+            }
+        }
+
+        // Without the witness file, the recorded input names still say which
+        // binding the circuit uses.
+        let witness = witness.unwrap_or_else(|| {
+            Value::Object(self.inputs.iter().map(|k| (k.clone(), Value::Null)).collect())
+        });
+        let challenge = ChallengeLayout::current(&witness);
+        if challenge != self.challenge {
+            mismatches.push(ManifestMismatch::Challenge {
+                expected: self.challenge.clone(),
+                actual: challenge,
+            });
+        }
+
+        mismatches
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("JSON serialization failed")
+    }
+
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let raw = std::fs::read_to_string(path)?;
+        serde_json::from_str(&raw)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+}
+
+/// Hex-encoded SHA-256 of a file's contents.
+pub fn sha256_file(path: &Path) -> std::io::Result<String> {
+    Ok(sha256_hex(&std::fs::read(path)?))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(bytes).iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::witness_builder::{build_witness_input, write_witness_json};
+    use schnorr_core::{KeyPair, Signature};
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("schnorr-manifest-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn fresh_manifest_verifies_and_detects_tampering() {
+        let kp = KeyPair::generate();
+        let sig = Signature::sign(&kp, b"manifest");
+        let witness = build_witness_input(&sig, &kp, b"manifest");
+
+        let witness_path = temp_path("input.json");
+        let circuit_path = temp_path("circuit.circom");
+        write_witness_json(&witness, &witness_path).unwrap();
+        std::fs::write(&circuit_path, "template T() {}").unwrap();
+
+        let manifest = Manifest::for_witness(&witness, &witness_path)
+            .unwrap()
+            .with_artifact("circuit", &circuit_path)
+            .unwrap();
+        assert_eq!(manifest.challenge.layout, ["R.x", "PK.x", "PK.y", "msgHash"]);
+        assert!(manifest.verify().is_empty());
+
+        std::fs::write(&circuit_path, "template T() { signal input x; }").unwrap();
+        let mismatches = manifest.verify();
+        assert_eq!(mismatches.len(), 1);
+        assert!(matches!(&mismatches[0], ManifestMismatch::Hash { role, .. } if role == "circuit"));
+
+        std::fs::remove_file(&witness_path).unwrap();
+        std::fs::remove_file(&circuit_path).unwrap();
+    }
+
+    #[test]
+    fn absent_artifact_is_reported() {
+        let kp = KeyPair::generate();
+        let sig = Signature::sign(&kp, b"manifest");
+        let witness = build_witness_input(&sig, &kp, b"manifest");
+        let witness_path = temp_path("absent.json");
+        write_witness_json(&witness, &witness_path).unwrap();
+
+        let manifest = Manifest::for_witness(&witness, &witness_path)
+            .unwrap()
+            .with_absent_artifact("zkey", &temp_path("missing.zkey"));
+        let mismatches = manifest.verify();
+        assert_eq!(mismatches.len(), 1);
+        assert!(matches!(&mismatches[0], ManifestMismatch::Absent { role, .. } if role == "zkey"));
+
+        let back: Manifest = serde_json::from_str(&manifest.to_json()).unwrap();
+        assert_eq!(back, manifest);
+
+        std::fs::remove_file(&witness_path).unwrap();
+    }
+
+    #[test]
+    fn unwritten_witness_matches_written_bytes() {
+        let kp = KeyPair::generate();
+        let sig = Signature::sign(&kp, b"manifest");
+        let witness = build_witness_input(&sig, &kp, b"manifest");
+        let witness_path = temp_path("unwritten.json");
+        let wtns_path = temp_path("unwritten.wtns");
+        write_witness_json(&witness, &witness_path).unwrap();
+        std::fs::write(&wtns_path, b"wtns").unwrap();
+
+        let written = Manifest::for_witness(&witness, &witness_path).unwrap();
+        let unwritten = Manifest::for_unwritten_witness(&witness)
+            .with_artifact("wtns", &wtns_path)
+            .unwrap();
+        assert_eq!(unwritten.artifacts[0].sha256, written.artifacts[0].sha256);
+        assert_eq!(unwritten.artifacts[0].path, None);
+        assert!(unwritten.verify().is_empty());
+
+        std::fs::write(&wtns_path, b"other").unwrap();
+        assert_eq!(unwritten.verify().len(), 1);
+
+        std::fs::remove_file(&witness_path).unwrap();
+        std::fs::remove_file(&wtns_path).unwrap();
+    }
+
+    #[test]
+    fn different_source_is_a_code_mismatch() {
+        let kp = KeyPair::generate();
+        let sig = Signature::sign(&kp, b"manifest");
+        let witness = build_witness_input(&sig, &kp, b"manifest");
+
+        let mut manifest = Manifest::for_unwritten_witness(&witness);
+        assert_eq!(manifest.code.source_sha256.len(), 64);
+        assert!(manifest.verify().is_empty());
+
+        manifest.code.source_sha256 = "0".repeat(64);
+        let mismatches = manifest.verify();
+        assert!(matches!(&mismatches[..], [ManifestMismatch::Code { .. }]));
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let kp = KeyPair::generate();
+        let sig = Signature::sign(&kp, b"manifest");
+        let witness = build_witness_input(&sig, &kp, b"manifest");
+        let witness_path = temp_path("roundtrip.json");
+        write_witness_json(&witness, &witness_path).unwrap();
+
+        let manifest = Manifest::for_witness(&witness, &witness_path).unwrap();
+        let back: Manifest = serde_json::from_str(&manifest.to_json()).unwrap();
+        assert_eq!(back, manifest);
+
+        std::fs::remove_file(&witness_path).unwrap();
+    }
+}
@@ -0,0 +1,552 @@
+// crates/schnorr-witness/src/wasm_witness.rs
+//
+// In-process circom witness calculator (feature "wasm").
+//
+// Runs the circom-generated `<circuit>_js/<circuit>.wasm` under wasmtime, so
+// a signature goes straight to the full witness vector / `.wtns` file
+// without node or an intermediate input.json.  This is a port of the
+// `witness_calculator.js` that circom emits alongside the wasm:
+//
+//   - inputs are addressed by the 64-bit FNV-1a hash of the signal name,
+//     split into (hMSB, hLSB);
+//   - field elements cross the boundary as n32 little-endian u32 words
+//     through the module's shared read/write memory;
+//   - the `runtime.*` imports report errors and `log()` output.
+//
+// A `WitnessCalculator` instance is reusable: every calculation calls
+// `init` first, which resets the circuit state.
+
+use std::fmt;
+use std::path::Path;
+
+use num_bigint::{BigInt, BigUint, Sign};
+use serde_json::Value;
+use wasmtime::{Caller, Engine, Instance, Linker, Module, Store};
+
+/// Errors from loading or running a witness calculator.
+#[derive(Debug)]
+pub enum WasmWitnessError {
+    Io(std::io::Error),
+    /// Compilation, instantiation or a trap inside the circuit
+    /// (e.g. a failed constraint assertion).
+    Wasm(String),
+    /// An input could not be mapped onto the circuit's signals.
+    Input(String),
+}
+
+impl fmt::Display for WasmWitnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WasmWitnessError::Io(e) => write!(f, "cannot read wasm: {e}"),
+            WasmWitnessError::Wasm(e) => write!(f, "witness calculation failed: {e}"),
+            WasmWitnessError::Input(e) => write!(f, "invalid circuit input: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for WasmWitnessError {}
+
+impl From<std::io::Error> for WasmWitnessError {
+    fn from(e: std::io::Error) -> Self {
+        WasmWitnessError::Io(e)
+    }
+}
+
+impl From<wasmtime::Error> for WasmWitnessError {
+    fn from(e: wasmtime::Error) -> Self {
+        WasmWitnessError::Wasm(format!("{e:#}"))
+    }
+}
+
+/// Messages accumulated by the `runtime.*` imports.
+#[derive(Default)]
+struct HostState {
+    err_msg: String,
+    log_msg: String,
+}
+
+/// A loaded circom witness calculator.
+pub struct WitnessCalculator {
+    store: Store<HostState>,
+    instance: Instance,
+    n32: u32,
+    prime: BigUint,
+    witness_size: u32,
+}
+
+impl WitnessCalculator {
+    /// Load `<circuit>.wasm` from disk.
+    pub fn from_file(path: &Path) -> Result<Self, WasmWitnessError> {
+        Self::new(&std::fs::read(path)?)
+    }
+
+    /// Compile and instantiate a circom witness calculator module.
+    pub fn new(wasm: &[u8]) -> Result<Self, WasmWitnessError> {
+        let engine = Engine::default();
+        let module = Module::new(&engine, wasm)?;
+        let mut linker: Linker<HostState> = Linker::new(&engine);
+
+        linker.func_wrap(
+            "runtime",
+            "exceptionHandler",
+            |mut caller: Caller<'_, HostState>, code: i32| -> wasmtime::Result<()> {
+                let err = match code {
+                    1 => "Signal not found.",
+                    2 => "Too many signals set.",
+                    3 => "Signal already set.",
+                    4 => "Assert Failed.",
+                    5 => "Not enough memory.",
+                    6 => "Input signal array access exceeds the size.",
+                    _ => "Unknown error.",
+                };
+                let detail = std::mem::take(&mut caller.data_mut().err_msg);
+                Err(wasmtime::Error::msg(format!("{err}\n{detail}")))
+            },
+        )?;
+        linker.func_wrap(
+            "runtime",
+            "printErrorMessage",
+            |mut caller: Caller<'_, HostState>| -> wasmtime::Result<()> {
+                let msg = read_message(&mut caller)?;
+                caller.data_mut().err_msg.push_str(&msg);
+                caller.data_mut().err_msg.push('\n');
+                Ok(())
+            },
+        )?;
+        linker.func_wrap(
+            "runtime",
+            "writeBufferMessage",
+            |mut caller: Caller<'_, HostState>| -> wasmtime::Result<()> {
+                let msg = read_message(&mut caller)?;
+                // Every circom `log()` ends with a lone "\n" message.
+                if msg == "\n" {
+                    eprintln!("{}", std::mem::take(&mut caller.data_mut().log_msg));
+                } else {
+                    append_log(caller.data_mut(), &msg);
+                }
+                Ok(())
+            },
+        )?;
+        linker.func_wrap(
+            "runtime",
+            "showSharedRWMemory",
+            |mut caller: Caller<'_, HostState>| -> wasmtime::Result<()> {
+                let n32 = call_i32(&mut caller, "getFieldNumLen32")? as u32;
+                let value = read_shared_caller(&mut caller, n32)?;
+                append_log(caller.data_mut(), &value.to_string());
+                Ok(())
+            },
+        )?;
+
+        let mut store = Store::new(&engine, HostState::default());
+        let instance = linker.instantiate(&mut store, &module)?;
+
+        let mut calc = WitnessCalculator {
+            store,
+            instance,
+            n32: 0,
+            prime: BigUint::default(),
+            witness_size: 0,
+        };
+        calc.n32 = calc.export_i32("getFieldNumLen32")? as u32;
+        calc.call_unit("getRawPrime")?;
+        calc.prime = calc.read_shared()?;
+        calc.witness_size = calc.export_i32("getWitnessSize")? as u32;
+        Ok(calc)
+    }
+
+    /// Circom version the module was compiled with.
+    pub fn circom_version(&mut self) -> Result<u32, WasmWitnessError> {
+        Ok(self.export_i32("getVersion")? as u32)
+    }
+
+    /// Field modulus the circuit works over.
+    pub fn prime(&self) -> &BigUint {
+        &self.prime
+    }
+
+    /// Number of signals in the full witness vector.
+    pub fn witness_size(&self) -> u32 {
+        self.witness_size
+    }
+
+    /// Compute the full witness vector for a circuit input object
+    /// (the same JSON shape `generate_witness.js` takes).
+    pub fn calculate_witness(&mut self, input: &Value) -> Result<Vec<BigUint>, WasmWitnessError> {
+        self.set_inputs(input)?;
+        let mut witness = Vec::with_capacity(self.witness_size as usize);
+        for i in 0..self.witness_size {
+            self.call_i32_arg("getWitness", i as i32)?;
+            witness.push(self.read_shared()?);
+        }
+        Ok(witness)
+    }
+
+    /// Compute the witness and encode it as a snarkjs `.wtns` file.
+    pub fn calculate_wtns_bin(&mut self, input: &Value) -> Result<Vec<u8>, WasmWitnessError> {
+        let witness = self.calculate_witness(input)?;
+        Ok(encode_wtns(&self.prime, self.n32, &witness))
+    }
+
+    fn set_inputs(&mut self, input: &Value) -> Result<(), WasmWitnessError> {
+        self.call_i32_arg("init", 0)?;
+
+        let mut signals: Vec<(String, Vec<Value>)> = Vec::new();
+        qualify_input("", input, &mut signals)?;
+
+        let mut input_counter = 0u32;
+        for (name, values) in &signals {
+            let h = fnv1a_64(name);
+            let (h_msb, h_lsb) = ((h >> 32) as u32 as i32, h as u32 as i32);
+
+            let size = self.typed_call::<(i32, i32), i32>("getInputSignalSize", (h_msb, h_lsb))?;
+            if size < 0 {
+                return Err(WasmWitnessError::Input(format!("signal {name} not found")));
+            }
+            if values.len() != size as usize {
+                return Err(WasmWitnessError::Input(format!(
+                    "signal {name} expects {size} values, got {}",
+                    values.len()
+                )));
+            }
+
+            for (i, v) in values.iter().enumerate() {
+                let value = self.normalize(name, v)?;
+                self.write_shared(&value)?;
+                self.typed_call::<(i32, i32, i32), ()>("setInputSignal", (h_msb, h_lsb, i as i32))?;
+                input_counter += 1;
+            }
+        }
+
+        let expected = self.export_i32("getInputSize")? as u32;
+        if input_counter < expected {
+            return Err(WasmWitnessError::Input(format!(
+                "not all inputs have been set: only {input_counter} out of {expected}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reduce an input value into [0, p).
+    fn normalize(&self, name: &str, v: &Value) -> Result<BigUint, WasmWitnessError> {
+        let raw = match v {
+            Value::String(s) => s.clone(),
+            Value::Number(n) => n.to_string(),
+            Value::Bool(b) => u8::from(*b).to_string(),
+            _ => {
+                return Err(WasmWitnessError::Input(format!(
+                    "signal {name} is not a number"
+                )))
+            }
+        };
+        let n: BigInt = raw.parse().map_err(|_| {
+            WasmWitnessError::Input(format!("signal {name} is not an integer: {raw}"))
+        })?;
+        let p = BigInt::from_biguint(Sign::Plus, self.prime.clone());
+        let reduced = ((n % &p) + &p) % &p;
+        Ok(reduced.to_biguint().expect("reduced value is non-negative"))
+    }
+
+    fn read_shared(&mut self) -> Result<BigUint, WasmWitnessError> {
+        let mut words = Vec::with_capacity(self.n32 as usize);
+        for j in 0..self.n32 {
+            words.push(self.typed_call::<i32, i32>("readSharedRWMemory", j as i32)? as u32);
+        }
+        Ok(BigUint::from_slice(&words))
+    }
+
+    fn write_shared(&mut self, value: &BigUint) -> Result<(), WasmWitnessError> {
+        let mut words = value.to_u32_digits();
+        words.resize(self.n32 as usize, 0);
+        for (j, w) in words.iter().enumerate() {
+            self.typed_call::<(i32, i32), ()>("writeSharedRWMemory", (j as i32, *w as i32))?;
+        }
+        Ok(())
+    }
+
+    fn export_i32(&mut self, name: &str) -> Result<i32, WasmWitnessError> {
+        self.typed_call::<(), i32>(name, ())
+    }
+
+    fn call_unit(&mut self, name: &str) -> Result<(), WasmWitnessError> {
+        self.typed_call::<(), ()>(name, ())
+    }
+
+    fn call_i32_arg(&mut self, name: &str, arg: i32) -> Result<(), WasmWitnessError> {
+        self.typed_call::<i32, ()>(name, arg)
+    }
+
+    fn typed_call<P, R>(&mut self, name: &str, params: P) -> Result<R, WasmWitnessError>
+    where
+        P: wasmtime::WasmParams,
+        R: wasmtime::WasmResults,
+    {
+        let func = self
+            .instance
+            .get_typed_func::<P, R>(&mut self.store, name)?;
+        Ok(func.call(&mut self.store, params)?)
+    }
+}
+
+/// Encode a witness vector in the snarkjs `.wtns` v2 format.
+///
+///   "wtns" | version=2 | nSections=2
+///   section 1: n8 | prime | witness count
+///   section 2: witness values, n8 bytes each, little-endian
+pub fn encode_wtns(prime: &BigUint, n32: u32, witness: &[BigUint]) -> Vec<u8> {
+    let n8 = n32 * 4;
+    let mut out = Vec::with_capacity(64 + witness.len() * n8 as usize);
+
+    out.extend_from_slice(b"wtns");
+    out.extend_from_slice(&2u32.to_le_bytes());
+    out.extend_from_slice(&2u32.to_le_bytes());
+
+    out.extend_from_slice(&1u32.to_le_bytes());
+    out.extend_from_slice(&(8 + n8 as u64).to_le_bytes());
+    out.extend_from_slice(&n8.to_le_bytes());
+    push_field(&mut out, prime, n8);
+    out.extend_from_slice(&(witness.len() as u32).to_le_bytes());
+
+    out.extend_from_slice(&2u32.to_le_bytes());
+    out.extend_from_slice(&(n8 as u64 * witness.len() as u64).to_le_bytes());
+    for w in witness {
+        push_field(&mut out, w, n8);
+    }
+    out
+}
+
+fn push_field(out: &mut Vec<u8>, value: &BigUint, n8: u32) {
+    let mut bytes = value.to_bytes_le();
+    bytes.resize(n8 as usize, 0);
+    out.extend_from_slice(&bytes);
+}
+
+/// Flatten an input object into (qualified signal name, values) pairs,
+/// following circom's `qualify_input`: nested objects join with ".",
+/// arrays of objects index as "[i]", scalar arrays stay one signal.
+fn qualify_input(
+    prefix: &str,
+    input: &Value,
+    out: &mut Vec<(String, Vec<Value>)>,
+) -> Result<(), WasmWitnessError> {
+    match input {
+        Value::Object(obj) => {
+            for (k, v) in obj {
+                let name = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{prefix}.{k}")
+                };
+                qualify_input(&name, v, out)?;
+            }
+        }
+        Value::Array(_) => {
+            let mut flat = Vec::new();
+            flatten(input, &mut flat);
+            if flat.iter().any(Value::is_object) {
+                if !flat.iter().all(Value::is_object) {
+                    return Err(WasmWitnessError::Input(format!(
+                        "types are not the same in the key {prefix}"
+                    )));
+                }
+                if let Value::Array(items) = input {
+                    for (i, item) in items.iter().enumerate() {
+                        qualify_input(&format!("{prefix}[{i}]"), item, out)?;
+                    }
+                }
+            } else {
+                out.push((prefix.to_string(), flat));
+            }
+        }
+        scalar => out.push((prefix.to_string(), vec![scalar.clone()])),
+    }
+    Ok(())
+}
+
+fn flatten(v: &Value, out: &mut Vec<Value>) {
+    match v {
+        Value::Array(items) => items.iter().for_each(|i| flatten(i, out)),
+        other => out.push(other.clone()),
+    }
+}
+
+/// 64-bit FNV-1a, as used by circom to address input signals.
+fn fnv1a_64(s: &str) -> u64 {
+    let mut hash: u64 = 0xCBF2_9CE4_8422_2325;
+    for b in s.bytes() {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01B3);
+    }
+    hash
+}
+
+fn append_log(state: &mut HostState, msg: &str) {
+    if !state.log_msg.is_empty() {
+        state.log_msg.push(' ');
+    }
+    state.log_msg.push_str(msg);
+}
+
+fn call_i32(caller: &mut Caller<'_, HostState>, name: &str) -> wasmtime::Result<i32> {
+    let func = caller
+        .get_export(name)
+        .and_then(|e| e.into_func())
+        .ok_or_else(|| wasmtime::Error::msg(format!("missing export {name}")))?;
+    func.typed::<(), i32>(&caller)?.call(&mut *caller, ())
+}
+
+/// Drain the module's pending message (NUL-terminated, one char per call).
+fn read_message(caller: &mut Caller<'_, HostState>) -> wasmtime::Result<String> {
+    let mut msg = String::new();
+    loop {
+        let c = call_i32(caller, "getMessageChar")?;
+        if c == 0 {
+            return Ok(msg);
+        }
+        msg.push(char::from_u32(c as u32).unwrap_or(char::REPLACEMENT_CHARACTER));
+    }
+}
+
+fn read_shared_caller(caller: &mut Caller<'_, HostState>, n32: u32) -> wasmtime::Result<BigUint> {
+    let func = caller
+        .get_export("readSharedRWMemory")
+        .and_then(|e| e.into_func())
+        .ok_or_else(|| wasmtime::Error::msg("missing export readSharedRWMemory"))?;
+    let func = func.typed::<i32, i32>(&caller)?;
+    let mut words = Vec::with_capacity(n32 as usize);
+    for j in 0..n32 {
+        words.push(func.call(&mut *caller, j as i32)? as u32);
+    }
+    Ok(BigUint::from_slice(&words))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Minimal stand-in for a circom module: inputs `a` and `b`, witness
+    /// [1, a, b], prime 2^31 − 1, and an "assertion" that a != 0.
+    const MOCK_CIRCUIT: &str = r#"
+    (module
+      (import "runtime" "exceptionHandler" (func $exc (param i32)))
+      (import "runtime" "printErrorMessage" (func $perr))
+      (import "runtime" "writeBufferMessage" (func $wbuf))
+      (import "runtime" "showSharedRWMemory" (func $show))
+      (memory 1)
+      (global $set (mut i32) (i32.const 0))
+      (func (export "getVersion") (result i32) (i32.const 2))
+      (func (export "getFieldNumLen32") (result i32) (i32.const 8))
+      (func (export "getRawPrime")
+        (memory.fill (i32.const 0) (i32.const 0) (i32.const 32))
+        (i32.store (i32.const 0) (i32.const 0x7fffffff)))
+      (func (export "readSharedRWMemory") (param $i i32) (result i32)
+        (i32.load (i32.shl (local.get $i) (i32.const 2))))
+      (func (export "writeSharedRWMemory") (param $i i32) (param $v i32)
+        (i32.store (i32.shl (local.get $i) (i32.const 2)) (local.get $v)))
+      (func (export "getMessageChar") (result i32) (i32.const 0))
+      (func (export "init") (param i32)
+        (global.set $set (i32.const 0))
+        (memory.fill (i32.const 64) (i32.const 0) (i32.const 96))
+        (i32.store (i32.const 64) (i32.const 1)))
+      (func $slot (param $msb i32) (param $lsb i32) (result i32)
+        (if (result i32)
+          (i32.and (i32.eq (local.get $msb) (i32.const -1352410036))
+                   (i32.eq (local.get $lsb) (i32.const -2046694260)))
+          (then (i32.const 1))
+          (else
+            (if (result i32)
+              (i32.and (i32.eq (local.get $msb) (i32.const -1352409268))
+                       (i32.eq (local.get $lsb) (i32.const -2046692955)))
+              (then (i32.const 2))
+              (else (i32.const -1))))))
+      (func (export "getInputSignalSize") (param $msb i32) (param $lsb i32) (result i32)
+        (if (result i32) (i32.lt_s (call $slot (local.get $msb) (local.get $lsb)) (i32.const 0))
+          (then (i32.const -1))
+          (else (i32.const 1))))
+      (func (export "setInputSignal") (param $msb i32) (param $lsb i32) (param $pos i32)
+        (memory.copy
+          (i32.add (i32.const 64)
+                   (i32.shl (call $slot (local.get $msb) (local.get $lsb)) (i32.const 5)))
+          (i32.const 0) (i32.const 32))
+        (global.set $set (i32.add (global.get $set) (i32.const 1)))
+        (if (i32.eq (global.get $set) (i32.const 2))
+          (then (if (i32.eqz (i32.load (i32.const 96)))
+            (then (call $exc (i32.const 4)))))))
+      (func (export "getInputSize") (result i32) (i32.const 2))
+      (func (export "getWitnessSize") (result i32) (i32.const 3))
+      (func (export "getWitness") (param $i i32)
+        (memory.copy (i32.const 0)
+          (i32.add (i32.const 64) (i32.shl (local.get $i) (i32.const 5)))
+          (i32.const 32)))
+    )"#;
+
+    fn mock() -> WitnessCalculator {
+        WitnessCalculator::new(MOCK_CIRCUIT.as_bytes()).expect("mock circuit loads")
+    }
+
+    #[test]
+    fn fnv_matches_circom() {
+        assert_eq!(fnv1a_64("a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn computes_witness_and_normalizes_inputs() {
+        let mut calc = mock();
+        assert_eq!(calc.prime(), &BigUint::from(0x7fff_ffffu32));
+
+        let w = calc
+            .calculate_witness(&json!({ "a": "5", "b": "-1" }))
+            .unwrap();
+        assert_eq!(
+            w,
+            vec![1u32.into(), 5u32.into(), BigUint::from(0x7fff_fffeu32)]
+        );
+
+        // The instance is reusable.
+        let w = calc
+            .calculate_witness(&json!({ "a": 7, "b": "2" }))
+            .unwrap();
+        assert_eq!(w[1], 7u32.into());
+    }
+
+    #[test]
+    fn assertion_failure_surfaces_as_error() {
+        let err = mock()
+            .calculate_witness(&json!({ "a": "0", "b": "1" }))
+            .unwrap_err();
+        assert!(err.to_string().contains("Assert Failed"), "{err}");
+    }
+
+    #[test]
+    fn unknown_and_missing_signals_rejected() {
+        let mut calc = mock();
+        assert!(matches!(
+            calc.calculate_witness(&json!({ "a": "1", "c": "1" })),
+            Err(WasmWitnessError::Input(_))
+        ));
+        assert!(matches!(
+            calc.calculate_witness(&json!({ "a": "1" })),
+            Err(WasmWitnessError::Input(_))
+        ));
+    }
+
+    #[test]
+    fn wtns_layout() {
+        let mut calc = mock();
+        let bin = calc
+            .calculate_wtns_bin(&json!({ "a": "5", "b": "6" }))
+            .unwrap();
+        assert_eq!(&bin[..4], b"wtns");
+        // header (12) + section 1 (12 + n8 4 + prime 32 + count 4)
+        // + section 2 header (12) + 3 × 32
+        assert_eq!(bin.len(), 12 + 52 + 12 + 96);
+        // n8 and witness count
+        assert_eq!(u32::from_le_bytes(bin[24..28].try_into().unwrap()), 32);
+        assert_eq!(u32::from_le_bytes(bin[60..64].try_into().unwrap()), 3);
+    }
+}
@@ -1,11 +1,15 @@
 use ark_ff::{BigInteger, PrimeField};
 use num_bigint::BigUint;
 use serde_json::{json, Value};
+use std::fmt;
 use std::path::Path;
 
-use schnorr_core::hash::hash_message_to_field;
-use schnorr_core::keypair::KeyPair;
+use schnorr_core::binding::ChainBinding;
+use schnorr_core::envelope::{SignedMessage, SigningDomain};
+use schnorr_core::hash::{context_tag, hash_message_to_field};
+use schnorr_core::keypair::{KeyPair, PublicKey};
 use schnorr_core::sign::Signature;
+use schnorr_core::verify::VerifyResult;
 
 /// Convert any PrimeField element to a decimal string for Circom JSON.
 fn field_to_dec<F: PrimeField>(f: &F) -> String {
@@ -14,8 +18,17 @@ fn field_to_dec<F: PrimeField>(f: &F) -> String {
 }
 
 pub fn build_witness_input(sig: &Signature, keypair: &KeyPair, message: &[u8]) -> Value {
+    build_witness_input_for_public_key(sig, &keypair.pk, message)
+}
+
+/// [`build_witness_input`] for callers that only hold the public key.
+pub fn build_witness_input_for_public_key(
+    sig: &Signature,
+    public_key: &PublicKey,
+    message: &[u8],
+) -> Value {
     let msg_hash = hash_message_to_field(message);
-    let (pk_x, pk_y) = keypair.pk.coords();
+    let (pk_x, pk_y) = public_key.coords();
 
     // s is ark_ed_on_bn254::Fr — its integer value is < n < p,
     // so the decimal string is the same whether viewed in Z_n or F_p.
@@ -33,6 +46,106 @@ pub fn build_witness_input(sig: &Signature, keypair: &KeyPair, message: &[u8]) -
     })
 }
 
+/// Build the witness input for a signature made with
+/// [`Signature::sign_with_context`].
+///
+/// `msgHash` stays the raw H(m); the extra `ctxTag` input lets
+/// `circuits/schnorr_ctx.circom` recompute Poseidon(ctxTag, msgHash) itself,
+/// so the proof binds the same domain the signer hashed.
+pub fn build_witness_input_with_context(
+    sig: &Signature,
+    keypair: &KeyPair,
+    context: &[u8],
+    message: &[u8],
+) -> Value {
+    let mut witness = build_witness_input(sig, keypair, message);
+    insert_context_tag(&mut witness, context);
+    witness
+}
+
+/// Build the witness input for a signature made with
+/// [`Signature::sign_with_chain_binding`].
+///
+/// Emits `chainId` and `verifyingContract`; `circuits/schnorr_chain.circom`
+/// derives the domain tag from them, so the proof is tied to the deployment.
+pub fn build_witness_input_with_chain_binding(
+    sig: &Signature,
+    keypair: &KeyPair,
+    binding: &ChainBinding,
+    message: &[u8],
+) -> Value {
+    let mut witness = build_witness_input(sig, keypair, message);
+    insert_chain_binding(&mut witness, binding);
+    witness
+}
+
+/// Options for [`from_signed_message`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WitnessOptions {
+    /// Build the witness even if the envelope's signature does not verify
+    /// (e.g. to debug an unsatisfiable circuit with `witness_diff`).
+    pub skip_verification: bool,
+}
+
+/// Why a witness input could not be built from an envelope.
+#[derive(Debug, PartialEq, Eq)]
+pub enum WitnessError {
+    /// The signature does not verify against the envelope's key and domain.
+    InvalidSignature,
+    /// The signature's challenge profile has no circuit.
+    UnsupportedProfile(&'static str),
+}
+
+impl fmt::Display for WitnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WitnessError::InvalidSignature => write!(f, "signature does not verify"),
+            WitnessError::UnsupportedProfile(id) => {
+                write!(f, "no circuit for challenge profile {id}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WitnessError {}
+
+/// Build the witness input for a [`SignedMessage`] envelope.
+///
+/// The envelope's domain selects the circuit inputs (`ctxTag` for
+/// `schnorr_ctx.circom`, `chainId`/`verifyingContract` for
+/// `schnorr_chain.circom`).  No secret key is needed.
+pub fn from_signed_message(
+    signed: &SignedMessage,
+    options: WitnessOptions,
+) -> Result<Value, WitnessError> {
+    let sig = &signed.signature;
+    if !sig.profile.is_default() {
+        return Err(WitnessError::UnsupportedProfile(sig.profile.id));
+    }
+    if !options.skip_verification && signed.verify() != VerifyResult::Valid {
+        return Err(WitnessError::InvalidSignature);
+    }
+
+    let mut witness =
+        build_witness_input_for_public_key(sig, &signed.public_key, &signed.message);
+    match &signed.domain {
+        SigningDomain::None => {}
+        SigningDomain::Context(ctx) => insert_context_tag(&mut witness, ctx),
+        SigningDomain::Chain(binding) => insert_chain_binding(&mut witness, binding),
+    }
+    Ok(witness)
+}
+
+fn insert_context_tag(witness: &mut Value, context: &[u8]) {
+    witness["ctxTag"] = Value::String(field_to_dec(&context_tag(context)));
+}
+
+fn insert_chain_binding(witness: &mut Value, binding: &ChainBinding) {
+    let (chain_id, contract) = binding.to_dec_strings();
+    witness["chainId"] = Value::String(chain_id);
+    witness["verifyingContract"] = Value::String(contract);
+}
+
 /// Build witness JSON and write it to a file.
 pub fn export_witness_json(
     sig: &Signature,
@@ -41,8 +154,17 @@ pub fn export_witness_json(
     output_path: &Path,
 ) -> std::io::Result<()> {
     let witness = build_witness_input(sig, keypair, message);
-    let json_str = serde_json::to_string_pretty(&witness).expect("JSON serialization failed");
-    std::fs::write(output_path, json_str)
+    write_witness_json(&witness, output_path)
+}
+
+/// The exact bytes [`write_witness_json`] writes.
+pub fn witness_json(witness: &Value) -> String {
+    serde_json::to_string_pretty(witness).expect("JSON serialization failed")
+}
+
+/// Write an already-built witness input to a file as pretty JSON.
+pub fn write_witness_json(witness: &Value, output_path: &Path) -> std::io::Result<()> {
+    std::fs::write(output_path, witness_json(witness))
 }
 
 // ---------------------------------------------------------------------------
@@ -95,4 +217,72 @@ mod tests {
         let j2 = build_witness_input(&sig, &kp, msg);
         assert_eq!(j1, j2);
     }
+
+    #[test]
+    fn context_witness_has_ctx_tag() {
+        let kp = KeyPair::generate();
+        let msg = b"test";
+        let sig = Signature::sign_with_context(&kp, b"app", msg);
+
+        let plain = build_witness_input(&sig, &kp, msg);
+        let json = build_witness_input_with_context(&sig, &kp, b"app", msg);
+
+        assert_eq!(json["msgHash"], plain["msgHash"], "msgHash must stay the raw H(m)");
+        assert_eq!(
+            json["ctxTag"].as_str().unwrap(),
+            field_to_dec(&context_tag(b"app"))
+        );
+    }
+
+    #[test]
+    fn chain_binding_witness_has_domain_inputs() {
+        let kp = KeyPair::generate();
+        let binding = ChainBinding::new(31337, [0xAB; 20]);
+        let sig = Signature::sign_with_chain_binding(&kp, &binding, b"test");
+
+        let json = build_witness_input_with_chain_binding(&sig, &kp, &binding, b"test");
+        assert_eq!(json["chainId"], "31337");
+        assert_eq!(json["verifyingContract"], field_to_dec(&binding.contract_field()));
+        assert!(json.get("ctxTag").is_none(), "the circuit derives the tag itself");
+    }
+
+    #[test]
+    fn signed_message_matches_keypair_builders() {
+        let kp = KeyPair::generate();
+        let binding = ChainBinding::new(10, [0x42; 20]);
+
+        let plain = SignedMessage::sign(&kp, b"env", SigningDomain::None);
+        assert_eq!(
+            from_signed_message(&plain, WitnessOptions::default()).unwrap(),
+            build_witness_input(&plain.signature, &kp, b"env")
+        );
+
+        let ctx = SignedMessage::sign(&kp, b"env", SigningDomain::Context(b"app".to_vec()));
+        assert_eq!(
+            from_signed_message(&ctx, WitnessOptions::default()).unwrap(),
+            build_witness_input_with_context(&ctx.signature, &kp, b"app", b"env")
+        );
+
+        let chain = SignedMessage::sign(&kp, b"env", SigningDomain::Chain(binding));
+        assert_eq!(
+            from_signed_message(&chain, WitnessOptions::default()).unwrap(),
+            build_witness_input_with_chain_binding(&chain.signature, &kp, &binding, b"env")
+        );
+    }
+
+    #[test]
+    fn signed_message_with_bad_signature_is_rejected() {
+        let kp = KeyPair::generate();
+        let mut signed = SignedMessage::sign(&kp, b"env", SigningDomain::None);
+        signed.message = b"tampered".to_vec();
+
+        assert_eq!(
+            from_signed_message(&signed, WitnessOptions::default()),
+            Err(WitnessError::InvalidSignature)
+        );
+        let options = WitnessOptions {
+            skip_verification: true,
+        };
+        assert!(from_signed_message(&signed, options).is_ok());
+    }
 }
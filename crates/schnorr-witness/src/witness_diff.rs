@@ -0,0 +1,345 @@
+// crates/schnorr-witness/src/witness_diff.rs
+//
+// Compare two witness input JSON files signal by signal.
+//
+// Besides the raw inputs, each side is re-run through the verification
+// equation the circuit enforces:
+//
+//   R'  = s·G + e·PK                 (s_n = s mod n, e_n = e mod n)
+//   e'  = Poseidon(R'.x, pkX, pkY, msgHash')
+//
+// where msgHash' = Poseidon(ctxTag, msgHash) when a ctxTag input is present,
+// or Poseidon(Poseidon(chainId, verifyingContract), msgHash) for a
+// chain-bound witness.
+// The circuit only range-checks s to 253 bits, so s need not be reduced.
+// It is satisfiable iff e' == e, so the report shows R'.x, e_n, s, s_n and
+// e' next to each other for both sides.
+
+use std::fmt;
+
+use ark_bn254::Fr as Bn254Fr;
+use ark_ed_on_bn254::Fr as BjjFr;
+use ark_ff::{BigInteger, PrimeField};
+use num_bigint::{BigInt, BigUint};
+use serde_json::Value;
+
+use schnorr_core::curve::{bn254_to_bjj_scalar, bn254_to_dec_string, BjjPoint, BjjScalar};
+use schnorr_core::hash::{bind_context, schnorr_challenge};
+
+/// Bit width of the circuit's range check on s (`Num2Bits(253)`).
+const S_BITS: u64 = 253;
+
+/// Why a witness could not be analysed.
+#[derive(Debug, PartialEq, Eq)]
+pub enum WitnessParseError {
+    /// A required signal is absent.
+    Missing(String),
+    /// A signal is not a decimal integer below the field modulus.
+    NotCanonical(String),
+    /// A signal exceeds the bit width the circuit range-checks it to.
+    OutOfRange(String),
+    /// (pkX, pkY) is not a BabyJubJub point, so R' is undefined.
+    PkNotOnCurve,
+}
+
+impl fmt::Display for WitnessParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WitnessParseError::Missing(k) => write!(f, "missing signal {k}"),
+            WitnessParseError::NotCanonical(k) => write!(f, "signal {k} is not a canonical field element"),
+            WitnessParseError::OutOfRange(k) => write!(f, "signal {k} is out of the circuit's range"),
+            WitnessParseError::PkNotOnCurve => write!(f, "PK is not on the curve"),
+        }
+    }
+}
+
+impl std::error::Error for WitnessParseError {}
+
+/// One input signal whose value differs between the two witnesses.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SignalDiff {
+    pub signal: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+    /// right − left as a signed integer, when both sides are integers.
+    pub delta: Option<String>,
+}
+
+/// Values the circuit derives from a witness while checking it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Intermediates {
+    /// x-coordinate of R' = s·G + e·PK.
+    pub r_x: String,
+    /// e mod n, the scalar multiplied into PK.
+    pub e_n: String,
+    /// s as given; the circuit accepts any s < 2^253.
+    pub s: String,
+    /// s mod n, the scalar multiplied into G.
+    pub s_n: String,
+    /// Poseidon(R'.x, pkX, pkY, msgHash'), which must equal e.
+    pub e_recomputed: String,
+    pub consistent: bool,
+}
+
+/// Full comparison of two witnesses.
+#[derive(Debug)]
+pub struct WitnessDiff {
+    pub signals: Vec<SignalDiff>,
+    pub left: Result<Intermediates, WitnessParseError>,
+    pub right: Result<Intermediates, WitnessParseError>,
+}
+
+/// Compare two witness input objects.
+pub fn diff(left: &Value, right: &Value) -> WitnessDiff {
+    WitnessDiff {
+        signals: diff_signals(left, right),
+        left: intermediates(left),
+        right: intermediates(right),
+    }
+}
+
+/// List the input signals that differ, in key order.
+pub fn diff_signals(left: &Value, right: &Value) -> Vec<SignalDiff> {
+    let mut keys: Vec<&String> = Vec::new();
+    for obj in [left.as_object(), right.as_object()].into_iter().flatten() {
+        keys.extend(obj.keys());
+    }
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let l = signal_str(left, key);
+            let r = signal_str(right, key);
+            if l == r {
+                return None;
+            }
+            let delta = match (&l, &r) {
+                (Some(l), Some(r)) => match (l.parse::<BigInt>(), r.parse::<BigInt>()) {
+                    (Ok(l), Ok(r)) => Some(signed(&(r - l))),
+                    _ => None,
+                },
+                _ => None,
+            };
+            Some(SignalDiff {
+                signal: key.clone(),
+                left: l,
+                right: r,
+                delta,
+            })
+        })
+        .collect()
+}
+
+/// Re-run the circuit's verification equation over a witness.
+///
+/// An off-curve PK is reported rather than fed to the curve arithmetic,
+/// which is only defined for points on the curve.
+pub fn intermediates(witness: &Value) -> Result<Intermediates, WitnessParseError> {
+    let pk = BjjPoint {
+        x: parse_field(witness, "pkX")?,
+        y: parse_field(witness, "pkY")?,
+    };
+    if !pk.is_on_curve() {
+        return Err(WitnessParseError::PkNotOnCurve);
+    }
+    let mut msg_hash: Bn254Fr = parse_field(witness, "msgHash")?;
+    if witness.get("chainId").is_some() {
+        let ctx_tag = bind_context(
+            &parse_field(witness, "chainId")?,
+            &parse_field(witness, "verifyingContract")?,
+        );
+        msg_hash = bind_context(&ctx_tag, &msg_hash);
+    } else if witness.get("ctxTag").is_some() {
+        let ctx_tag = parse_field(witness, "ctxTag")?;
+        msg_hash = bind_context(&ctx_tag, &msg_hash);
+    }
+    // Num2Bits(253) is the circuit's only constraint on s, and (s + n)·G =
+    // s·G, so any s below 2^253 is evaluated mod n.
+    let s_raw = parse_uint(witness, "s")?;
+    if s_raw.bits() > S_BITS {
+        return Err(WitnessParseError::OutOfRange("s".into()));
+    }
+    let s = BjjScalar(BjjFr::from_le_bytes_mod_order(&s_raw.to_bytes_le()));
+    let e: Bn254Fr = parse_field(witness, "e")?;
+
+    let r_prime = BjjPoint::generator()
+        .scalar_mul(&s)
+        .add(&pk.mul_by_bn254_scalar(&e));
+    let (r_x, _) = r_prime.coords();
+    let e_recomputed = schnorr_challenge(&r_x, &pk.x, &pk.y, &msg_hash);
+
+    Ok(Intermediates {
+        r_x: bn254_to_dec_string(&r_x),
+        e_n: bn254_to_bjj_scalar(&e).to_dec_string(),
+        s: s_raw.to_string(),
+        s_n: s.to_dec_string(),
+        e_recomputed: bn254_to_dec_string(&e_recomputed),
+        consistent: e_recomputed == e,
+    })
+}
+
+/// Read a signal as a string; circom accepts both strings and numbers.
+fn signal_str(witness: &Value, key: &str) -> Option<String> {
+    match witness.get(key)? {
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn parse_uint(witness: &Value, key: &str) -> Result<BigUint, WitnessParseError> {
+    let raw = signal_str(witness, key).ok_or_else(|| WitnessParseError::Missing(key.into()))?;
+    raw.parse()
+        .map_err(|_| WitnessParseError::NotCanonical(key.into()))
+}
+
+fn parse_field<F: PrimeField>(witness: &Value, key: &str) -> Result<F, WitnessParseError> {
+    let value = parse_uint(witness, key)?;
+    let modulus = BigUint::from_bytes_le(&F::MODULUS.to_bytes_le());
+    if value >= modulus {
+        return Err(WitnessParseError::NotCanonical(key.into()));
+    }
+    Ok(F::from_le_bytes_mod_order(&value.to_bytes_le()))
+}
+
+fn signed(v: &BigInt) -> String {
+    if v.sign() == num_bigint::Sign::Minus {
+        v.to_string()
+    } else {
+        format!("+{v}")
+    }
+}
+
+impl fmt::Display for WitnessDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.signals.is_empty() {
+            writeln!(f, "Input signals: identical")?;
+        } else {
+            writeln!(f, "Input signals: {} differ", self.signals.len())?;
+            for d in &self.signals {
+                writeln!(f, "  {}", d.signal)?;
+                writeln!(f, "    left  = {}", d.left.as_deref().unwrap_or("<missing>"))?;
+                writeln!(f, "    right = {}", d.right.as_deref().unwrap_or("<missing>"))?;
+                if let Some(delta) = &d.delta {
+                    writeln!(f, "    delta = {delta}")?;
+                }
+            }
+        }
+
+        writeln!(f)?;
+        writeln!(f, "Signing intermediates (R' = s·G + e·PK):")?;
+        if let (Ok(l), Ok(r)) = (&self.left, &self.right) {
+            let rows = [
+                ("R'.x", &l.r_x, &r.r_x),
+                ("e_n", &l.e_n, &r.e_n),
+                ("s", &l.s, &r.s),
+                ("s_n", &l.s_n, &r.s_n),
+                ("Poseidon(R'.x, PK, msgHash)", &l.e_recomputed, &r.e_recomputed),
+            ];
+            for (name, lv, rv) in rows {
+                let mark = if lv == rv { "" } else { "  (differs)" };
+                writeln!(f, "  {name}{mark}")?;
+                writeln!(f, "    left  = {lv}")?;
+                writeln!(f, "    right = {rv}")?;
+            }
+        }
+        for (side, res) in [("left", &self.left), ("right", &self.right)] {
+            match res {
+                Ok(i) => {
+                    let verdict = if i.consistent {
+                        "satisfies the circuit"
+                    } else {
+                        "does NOT satisfy the circuit (e' != e)"
+                    };
+                    writeln!(f, "  {side}: {verdict}")?;
+                }
+                Err(e) => writeln!(f, "  {side}: cannot evaluate ({e})")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::witness_builder::build_witness_input;
+    use schnorr_core::{KeyPair, Signature};
+
+    #[test]
+    fn identical_witnesses_have_no_diffs() {
+        let kp = KeyPair::generate();
+        let sig = Signature::sign(&kp, b"msg");
+        let w = build_witness_input(&sig, &kp, b"msg");
+
+        let d = diff(&w, &w);
+        assert!(d.signals.is_empty());
+        assert!(d.left.unwrap().consistent);
+    }
+
+    #[test]
+    fn tampered_s_reports_delta_and_inconsistency() {
+        let kp = KeyPair::generate();
+        let sig = Signature::sign(&kp, b"msg");
+        let good = build_witness_input(&sig, &kp, b"msg");
+
+        let mut bad = good.clone();
+        let s: BigUint = good["s"].as_str().unwrap().parse().unwrap();
+        bad["s"] = Value::String((s + 5u32).to_string());
+
+        let d = diff(&good, &bad);
+        assert_eq!(d.signals.len(), 1);
+        assert_eq!(d.signals[0].signal, "s");
+        assert_eq!(d.signals[0].delta.as_deref(), Some("+5"));
+        assert!(!d.right.unwrap().consistent);
+    }
+
+    #[test]
+    fn unreduced_s_still_satisfies() {
+        let kp = KeyPair::generate();
+        let sig = Signature::sign(&kp, b"msg");
+        let good = build_witness_input(&sig, &kp, b"msg");
+
+        let n: BigUint = schnorr_core::curve::BJJ_ORDER.parse().unwrap();
+        let s: BigUint = good["s"].as_str().unwrap().parse().unwrap();
+        let mut shifted = good.clone();
+        shifted["s"] = Value::String((&s + &n).to_string());
+
+        let d = diff(&good, &shifted);
+        let right = d.right.unwrap();
+        assert!(right.consistent);
+        assert_eq!(right.s, (&s + &n).to_string());
+        assert_eq!(right.s_n, s.to_string());
+
+        let mut wide = good.clone();
+        wide["s"] = Value::String((BigUint::from(1u8) << 253u32).to_string());
+        assert_eq!(intermediates(&wide).unwrap_err(), WitnessParseError::OutOfRange("s".into()));
+    }
+
+    #[test]
+    fn missing_signal_is_reported() {
+        let kp = KeyPair::generate();
+        let sig = Signature::sign(&kp, b"msg");
+        let good = build_witness_input(&sig, &kp, b"msg");
+        let mut bad = good.clone();
+        bad.as_object_mut().unwrap().remove("e");
+
+        let d = diff(&good, &bad);
+        assert_eq!(d.signals[0].right, None);
+        assert_eq!(d.right.unwrap_err(), WitnessParseError::Missing("e".into()));
+    }
+
+    #[test]
+    fn off_curve_pk_is_reported() {
+        let kp = KeyPair::generate();
+        let sig = Signature::sign(&kp, b"msg");
+        let good = build_witness_input(&sig, &kp, b"msg");
+        let mut bad = good.clone();
+        bad["pkX"] = Value::String("1".into());
+        bad["pkY"] = Value::String("1".into());
+
+        let d = diff(&good, &bad);
+        assert_eq!(d.right.unwrap_err(), WitnessParseError::PkNotOnCurve);
+        assert!(d.left.unwrap().consistent);
+    }
+}
@@ -1,11 +1,24 @@
 #!/usr/bin/env bash
 # scripts/e2e_test.sh — Full end-to-end test: Rust sign → Circom prove → verify
+#
+# For the context-separated circuit:
+#   CIRCUIT_NAME=schnorr_ctx CONTEXT=my-app scripts/e2e_test.sh
+#
+# For the chain-bound circuit:
+#   CIRCUIT_NAME=schnorr_chain CHAIN_ID=1 VERIFYING_CONTRACT=0x... scripts/e2e_test.sh
 set -euo pipefail
 
 SCRIPT_DIR="$(cd "$(dirname "$0")" && pwd)"
 PROJECT_DIR="$(dirname "$SCRIPT_DIR")"
 BUILD_DIR="${PROJECT_DIR}/build"
-CIRCUIT_NAME="schnorr"
+CIRCUIT_NAME="${CIRCUIT_NAME:-schnorr}"
+
+# The default circuit keeps the historical artifact names.
+if [ "$CIRCUIT_NAME" = "schnorr" ]; then
+    VKEY_FILE="${BUILD_DIR}/verification_key.json"
+else
+    VKEY_FILE="${BUILD_DIR}/${CIRCUIT_NAME}_verification_key.json"
+fi
 
 echo "╔══════════════════════════════════════════════════════╗"
 echo "║  Schnorr-BabyJubJub End-to-End Test                 ║"
@@ -36,7 +49,10 @@ echo ""
 echo "=== Step 3: Generating witness (Rust → JSON) ==="
 CARGO_TARGET_DIR=/tmp/bjj-schnorr-target cargo run -p schnorr-witness --release -- \
     --message "hello world" \
-    --output "${BUILD_DIR}/input.json" 2>&1
+    ${CONTEXT:+--context "$CONTEXT"} \
+    ${CHAIN_ID:+--chain-id "$CHAIN_ID" --verifying-contract "$VERIFYING_CONTRACT"} \
+    --output "${BUILD_DIR}/input.json" \
+    --manifest "${BUILD_DIR}/manifest.json" 2>&1
 echo "  ✓ Witness written to ${BUILD_DIR}/input.json"
 cat "${BUILD_DIR}/input.json"
 echo ""
@@ -47,7 +63,7 @@ echo ""
 echo "=== Step 4: Checking circuit artifacts ==="
 for f in "${BUILD_DIR}/${CIRCUIT_NAME}.r1cs" \
          "${BUILD_DIR}/${CIRCUIT_NAME}.zkey" \
-         "${BUILD_DIR}/verification_key.json" \
+         "$VKEY_FILE" \
          "${BUILD_DIR}/${CIRCUIT_NAME}_js/${CIRCUIT_NAME}.wasm"; do
     if [ ! -f "$f" ]; then
         echo "  ✗ Missing: $f"
@@ -56,6 +72,8 @@ for f in "${BUILD_DIR}/${CIRCUIT_NAME}.r1cs" \
     fi
 done
 echo "  ✓ All circuit artifacts present"
+CARGO_TARGET_DIR=/tmp/bjj-schnorr-target cargo run -q -p schnorr-witness --release -- \
+    verify-manifest "${BUILD_DIR}/manifest.json"
 echo ""
 
 # ──────────────────────────────────────────────────────────
@@ -86,7 +104,7 @@ echo ""
 # ──────────────────────────────────────────────────────────
 echo "=== Step 7: Verifying Groth16 proof ==="
 RESULT=$(npx snarkjs groth16 verify \
-    "${BUILD_DIR}/verification_key.json" \
+    "$VKEY_FILE" \
     "${BUILD_DIR}/public.json" \
     "${BUILD_DIR}/proof.json" 2>&1)
 echo "$RESULT"
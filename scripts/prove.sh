@@ -5,7 +5,7 @@ set -euo pipefail
 SCRIPT_DIR="$(cd "$(dirname "$0")" && pwd)"
 PROJECT_DIR="$(dirname "$SCRIPT_DIR")"
 BUILD_DIR="${PROJECT_DIR}/build"
-CIRCUIT_NAME="schnorr"
+CIRCUIT_NAME="${CIRCUIT_NAME:-schnorr}"
 
 INPUT_FILE="${1:-${BUILD_DIR}/input.json}"
 
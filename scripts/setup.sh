@@ -5,9 +5,19 @@ set -euo pipefail
 SCRIPT_DIR="$(cd "$(dirname "$0")" && pwd)"
 PROJECT_DIR="$(dirname "$SCRIPT_DIR")"
 BUILD_DIR="${PROJECT_DIR}/build"
-CIRCUIT_NAME="schnorr"
+CIRCUIT_NAME="${CIRCUIT_NAME:-schnorr}"
 PTAU_FILE="${BUILD_DIR}/pot14_final.ptau"
 
+# The default circuit keeps the historical artifact names.
+if [ "$CIRCUIT_NAME" = "schnorr" ]; then
+    VKEY_FILE="${BUILD_DIR}/verification_key.json"
+else
+    VKEY_FILE="${BUILD_DIR}/${CIRCUIT_NAME}_verification_key.json"
+fi
+# schnorr → SchnorrVerifier.sol, schnorr_ctx → SchnorrCtxVerifier.sol, ...
+VERIFIER_NAME="$(echo "$CIRCUIT_NAME" | awk -F_ '{for (i = 1; i <= NF; i++) printf "%s", toupper(substr($i, 1, 1)) substr($i, 2)}')Verifier"
+VERIFIER_FILE="${PROJECT_DIR}/contracts/${VERIFIER_NAME}.sol"
+
 echo "=== Step 1: Compile Circom circuit ==="
 mkdir -p "$BUILD_DIR"
 circom "${PROJECT_DIR}/circuits/${CIRCUIT_NAME}.circom" \
@@ -52,14 +62,14 @@ echo ""
 echo "=== Step 5: Export verification key ==="
 npx snarkjs zkey export verificationkey \
     "${BUILD_DIR}/${CIRCUIT_NAME}.zkey" \
-    "${BUILD_DIR}/verification_key.json"
+    "$VKEY_FILE"
 
 echo ""
 echo "=== Step 6: Generate Solidity verifier ==="
 mkdir -p "${PROJECT_DIR}/contracts"
 npx snarkjs zkey export solidityverifier \
     "${BUILD_DIR}/${CIRCUIT_NAME}.zkey" \
-    "${PROJECT_DIR}/contracts/SchnorrVerifier.sol"
+    "$VERIFIER_FILE"
 
 # Clean up intermediate zkey
 rm -f "${BUILD_DIR}/${CIRCUIT_NAME}_0.zkey"
@@ -69,6 +79,6 @@ echo "=== Setup complete ==="
 echo "Build artifacts in: ${BUILD_DIR}/"
 echo "  - ${CIRCUIT_NAME}.r1cs"
 echo "  - ${CIRCUIT_NAME}.zkey"
-echo "  - verification_key.json"
+echo "  - $(basename "$VKEY_FILE")"
 echo "  - ${CIRCUIT_NAME}_js/ (WASM witness generator)"
-echo "Solidity verifier: contracts/SchnorrVerifier.sol"
+echo "Solidity verifier: contracts/${VERIFIER_NAME}.sol"
@@ -5,9 +5,17 @@ set -euo pipefail
 SCRIPT_DIR="$(cd "$(dirname "$0")" && pwd)"
 PROJECT_DIR="$(dirname "$SCRIPT_DIR")"
 BUILD_DIR="${PROJECT_DIR}/build"
+CIRCUIT_NAME="${CIRCUIT_NAME:-schnorr}"
+
+# The default circuit keeps the historical artifact names.
+if [ "$CIRCUIT_NAME" = "schnorr" ]; then
+    VKEY_FILE="${BUILD_DIR}/verification_key.json"
+else
+    VKEY_FILE="${BUILD_DIR}/${CIRCUIT_NAME}_verification_key.json"
+fi
 
 echo "=== Verifying Groth16 proof ==="
 npx snarkjs groth16 verify \
-    "${BUILD_DIR}/verification_key.json" \
+    "$VKEY_FILE" \
     "${BUILD_DIR}/public.json" \
     "${BUILD_DIR}/proof.json"
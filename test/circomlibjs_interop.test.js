@@ -0,0 +1,68 @@
+// test/circomlibjs_interop.test.js — Rust-signed Schnorr signature checked with circomlibjs
+//
+// Runs the Rust witness builder, then verifies the exported public key and
+// signature using only circomlibjs primitives:
+//   R' = s·Base8 + e·PK,   e == Poseidon(R'.x, PK.x, PK.y, msgHash)
+const { test } = require("node:test");
+const assert = require("node:assert");
+const { execFileSync } = require("node:child_process");
+const fs = require("node:fs");
+const os = require("node:os");
+const path = require("node:path");
+const { buildBabyjub, buildPoseidon } = require("circomlibjs");
+
+const PROJECT_DIR = path.resolve(__dirname, "..");
+
+function runRust(message) {
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), "bjj-schnorr-"));
+    const witnessPath = path.join(dir, "input.json");
+    const pubkeyPath = path.join(dir, "pubkey.json");
+    execFileSync(
+        "cargo",
+        ["run", "-q", "-p", "schnorr-witness", "--",
+         "--message", message,
+         "--output", witnessPath,
+         "--export-pubkey", pubkeyPath],
+        { cwd: PROJECT_DIR, stdio: ["ignore", "ignore", "inherit"] },
+    );
+    return {
+        witness: JSON.parse(fs.readFileSync(witnessPath, "utf8")),
+        pubkey: JSON.parse(fs.readFileSync(pubkeyPath, "utf8")),
+    };
+}
+
+test("Rust public key exports match circomlibjs babyJub", async () => {
+    const babyJub = await buildBabyjub();
+    const F = babyJub.F;
+    const { witness, pubkey } = runRust("interop pubkey");
+
+    const A = [F.e(pubkey.point[0]), F.e(pubkey.point[1])];
+    assert.ok(babyJub.inCurve(A), "exported point must be on curve");
+    assert.strictEqual(pubkey.point[0], witness.pkX);
+    assert.strictEqual(pubkey.point[1], witness.pkY);
+
+    // Packed form agrees in both directions
+    assert.deepStrictEqual(Array.from(babyJub.packPoint(A)), pubkey.packed);
+    const unpacked = babyJub.unpackPoint(Uint8Array.from(pubkey.packed));
+    assert.ok(F.eq(unpacked[0], A[0]) && F.eq(unpacked[1], A[1]));
+});
+
+test("Rust Schnorr signature verifies with circomlibjs primitives", async () => {
+    const babyJub = await buildBabyjub();
+    const poseidon = await buildPoseidon();
+    const F = babyJub.F;
+    const { witness, pubkey } = runRust("interop signature");
+
+    const A = babyJub.unpackPoint(Uint8Array.from(pubkey.packed));
+    const sG = babyJub.mulPointEscalar(babyJub.Base8, BigInt(witness.s));
+    const ePK = babyJub.mulPointEscalar(A, BigInt(witness.e));
+    const R = babyJub.addPoint(sG, ePK);
+
+    const e = poseidon([
+        F.toObject(R[0]),
+        F.toObject(A[0]),
+        F.toObject(A[1]),
+        BigInt(witness.msgHash),
+    ]);
+    assert.strictEqual(poseidon.F.toObject(e).toString(), witness.e);
+});